@@ -0,0 +1,173 @@
+//! Code Status Core
+//!
+//! Definitions shared by the `code-status-macros` attributes and the
+//! `code-status-scanner` tool. Proc-macro crates can only export macros, so
+//! anything both sides need to agree on lives here.
+
+use std::{fmt, str::FromStr};
+
+/// Name of every attribute macro defined in `code-status-macros`, grouped by category
+pub const MACRO_NAMES: &[&str] = &[
+    // Code Quality Markers
+    "untested",
+    "includes_unwrap",
+    "needs",
+    "perf_critical",
+    "security_sensitive",
+    "unsafe_usage",
+    "no_clippy",
+    "complexity",
+    "allocation_heavy",
+    "panic_path",
+    "requires_cleanup_on_error",
+    // Review & Future Work Markers
+    "needs_review",
+    "temporary",
+    "assumptions",
+    "revisit_in",
+    "dependency_sensitive",
+    "platform_specific",
+    "feature_gated",
+    "api_stability",
+    "deadlock_risk",
+    "benchmark_candidate",
+    "requires_locale",
+    "requires_transaction",
+    "requires_review_before",
+    "requires_accessibility_review",
+    "reviewed",
+    "requires_documentation_review",
+    "requires_feature_documentation",
+    // Testing Markers
+    "requires_fuzzing",
+    "requires_backward_compat_test",
+    "requires_schema_migration_test",
+    "requires_property_test",
+    "needs_tests",
+    "coverage_gap",
+    // Performance Markers
+    "requires_capacity_planning",
+    "approximation",
+    "numeric_precision",
+    "requires_load_test",
+    "requires_metrics",
+    "observed",
+    "needs_benchmark_before_merge",
+    // Lifecycle Markers
+    "requires_feature_sunset",
+    "requires_migration",
+    "migration_complete",
+    "requires_feature_parity",
+    "legacy_compat",
+    "deprecated_since",
+    "replaces",
+    "breaking_change",
+    "requires_rollback_plan",
+    // Concurrency Markers
+    "data_race_risk",
+    "blocking_io",
+    "not_thread_safe",
+    "requires_ordering",
+    // Security Markers
+    "requires_permission_check",
+    "requires_secret",
+    "requires_input_sanitization",
+    // Resilience Markers
+    "requires_graceful_shutdown",
+    "requires_circuit_breaker",
+    "requires_chaos_test",
+    // Ownership Markers
+    "owner",
+];
+
+/// Name of the `#[status(...)]` attribute, which applies several markers at once
+///
+/// It isn't a marker itself, so it is not in `MACRO_NAMES`; the scanner reports
+/// each marker it lists instead.
+pub const STATUS_ATTRIBUTE: &str = "status";
+
+/// How urgently a marker deserves attention, ordered most to least severe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// Safety and security hazards that should block a release
+    Critical,
+    /// Known gaps that deserve attention soon
+    Warning,
+    /// Notes for context, such as stability or platform details
+    Info,
+}
+
+impl Severity {
+    /// All tiers, most severe first
+    pub const ALL: [Self; 3] = [Self::Critical, Self::Warning, Self::Info];
+
+    /// The default severity of a macro
+    ///
+    /// Names missing from `MACRO_NAMES` are `Info`.
+    pub fn of(macro_name: &str) -> Self {
+        match macro_name {
+            "security_sensitive"
+            | "unsafe_usage"
+            | "panic_path"
+            | "deadlock_risk"
+            | "requires_input_sanitization"
+            | "data_race_risk"
+            | "blocking_io"
+            | "not_thread_safe" => Self::Critical,
+            "untested"
+            | "coverage_gap"
+            | "needs_tests"
+            | "includes_unwrap"
+            | "needs"
+            | "perf_critical"
+            | "no_clippy"
+            | "complexity"
+            | "allocation_heavy"
+            | "needs_review"
+            | "requires_review_before"
+            | "temporary"
+            | "dependency_sensitive"
+            | "requires_fuzzing"
+            | "requires_feature_sunset"
+            | "requires_migration"
+            | "requires_feature_parity"
+            | "breaking_change"
+            | "requires_rollback_plan"
+            | "needs_benchmark_before_merge"
+            | "requires_ordering" => Self::Warning,
+            _ => Self::Info,
+        }
+    }
+
+    /// The lowercase name used on the command line and in config files
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Critical => "critical",
+            Self::Warning => "warning",
+            Self::Info => "info",
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Critical => "Critical",
+            Self::Warning => "Warning",
+            Self::Info => "Info",
+        };
+        f.pad(name)
+    }
+}
+
+impl FromStr for Severity {
+    type Err = String;
+
+    /// Parse a tier name, ignoring case
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|severity| severity.as_str().eq_ignore_ascii_case(name.trim()))
+            .ok_or_else(|| format!("expected critical, warning or info, got `{}`", name.trim()))
+    }
+}
@@ -18,6 +18,7 @@
 //! - [`complexity`] - Indicates high algorithm or cognitive complexity issues
 //! - [`allocation_heavy`] - Flags functions that perform significant heap allocations
 //! - [`panic_path`] - Highlights code paths that might panic under specific conditions
+//! - [`requires_cleanup_on_error`] - Marks code that must undo partial work when it fails
 //!
 //! ## Review & Future Work Markers
 //!
@@ -26,60 +27,298 @@
 //! - [`assumptions`] - Indicates code with non-obvious assumptions
 //! - [`revisit_in`] - Marks code that may need revisiting in a future version
 //! - [`dependency_sensitive`] - Marks code that's sensitive to changes in dependencies
-//! - [`platform_specific`] - Indicates code with behavior tied to specific platforms
-//! - [`feature_gated`] - Marks code dependent on specific feature flags
+//! - [`platform_specific`] - Compiles code only on the given platforms (or just documents them with `soft`)
+//! - [`feature_gated`] - Compiles code only when one of the given feature flags is enabled
 //! - [`api_stability`] - Indicates parts of the API that may change
 //! - [`deadlock_risk`] - Marks code with potential concurrency/deadlock issues
 //! - [`benchmark_candidate`] - Flags code that should be benchmarked and optimized
+//! - [`requires_locale`] - Flags locale-sensitive code for translation and i18n review
+//! - [`requires_transaction`] - Marks functions that must run inside a database transaction
+//! - [`requires_review_before`] - Sets a hard deadline for reviewing code (validated at compile time)
+//! - [`requires_accessibility_review`] - Flags UI code that needs an accessibility review
+//! - [`reviewed`] - Records that the concerns on an item were reviewed, and when and by whom (validated at compile time)
+//! - [`requires_documentation_review`] - Flags public docs or examples that need review before a docs release
+//! - [`requires_feature_documentation`] - Flags user-facing features that need release notes
+//!
+//! ## Testing Markers
+//!
+//! - [`requires_fuzzing`] - Flags code that should be covered by a fuzz target
+//! - [`requires_backward_compat_test`] - Flags behavior that needs a test proving older clients still work
+//! - [`requires_schema_migration_test`] - Flags schema changes that need a test running the migration against existing data
+//! - [`requires_property_test`] - Flags serializers, parsers and other code that needs a property-based test
+//! - [`needs_tests`] - Records how many tests an item has out of how many it needs (validated at compile time)
+//! - [`coverage_gap`] - Marks functions a coverage report should show as unexercised
+//!
+//! ## Performance Markers
+//!
+//! - [`requires_capacity_planning`] - Marks code whose cost scales with a deployment dimension
+//! - [`approximation`] - Marks numerical code that trades accuracy for speed
+//! - [`numeric_precision`] - Documents a precision choice (f32, f64, fixed-point) for review
+//! - [`requires_load_test`] - Flags paths that need a load test before a milestone
+//! - [`requires_metrics`] - Flags key paths that need metrics, like a latency histogram
+//! - [`observed`] - Records the metric or trace that already instruments a path
+//! - [`needs_benchmark_before_merge`] - Flags changes that need a benchmark run before they merge
+//!
+//! ## Lifecycle Markers
+//!
+//! - [`requires_feature_sunset`] - Marks code scheduled for removal on a given date
+//! - [`requires_migration`] - Marks code that depends on a pending migration
+//! - [`migration_complete`] - Records that a pending migration has been carried out
+//! - [`requires_feature_parity`] - Marks ported code that does not yet match its reference implementation
+//! - [`legacy_compat`] - Marks backward-compatibility shims kept for older clients
+//! - [`deprecated_since`] - Records the release an item was deprecated in
+//! - [`replaces`] - Links new code to the item it supersedes
+//! - [`breaking_change`] - Records an intentional breaking change to the public API
+//! - [`requires_rollback_plan`] - Flags changes that must not ship without a rollback plan
+//!
+//! ## Concurrency Markers
+//!
+//! - [`data_race_risk`] - Marks data shared across threads without proper synchronization
+//! - [`blocking_io`] - Marks blocking I/O, typically inside `async fn`s, to migrate to async I/O
+//! - [`not_thread_safe`] - Marks types that must never cross threads
+//! - [`requires_ordering`] - Marks code that relies on operations happening in a fixed order across threads
+//!
+//! ## Security Markers
+//!
+//! - [`requires_permission_check`] - Marks an authorization checkpoint and the permission it requires
+//! - [`requires_secret`] - Records a secret the code needs at runtime, for secret-management reviews
+//! - [`requires_input_sanitization`] - Marks code handling untrusted input that must be sanitized against an injection class (validated at compile time)
+//!
+//! ## Resilience Markers
+//!
+//! - [`requires_graceful_shutdown`] - Marks components that must shut down cleanly, e.g. by draining connections
+//! - [`requires_circuit_breaker`] - Marks calls to a downstream dependency that need a circuit breaker
+//! - [`requires_chaos_test`] - Flags code that needs a chaos experiment for a failure scenario
+//!
+//! ## Combined Markers
+//!
+//! - [`status`] - Applies several markers in one attribute, like `#[status(untested, needs = "hashing")]`
+//!
+//! ## Ownership Markers
+//!
+//! - [`owner`] - Names the team or person who owns an item (repeatable)
+//!
+//! # Supported Positions
+//!
+//! Every marker applies to items: functions, methods, types, traits, impls,
+//! modules, consts and statics, including items nested in a function body.
+//!
+//! Markers also accept a single statement or expression inside a function body,
+//! like the one `.unwrap()` call worth flagging:
+//!
+//! ```ignore
+//! #![feature(proc_macro_hygiene, stmt_expr_attributes)]
+//!
+//! fn port(config: &Config) -> u16 {
+//!     #[panic_path("port is validated at startup")]
+//!     let port = config.port.unwrap();
+//!     port
+//! }
+//! ```
+//!
+//! Stable Rust doesn't yet allow attribute macros there, so this needs a nightly
+//! compiler with the features above. Write expression statements as `let _ = ...;`,
+//! since the compiler drops the trailing `;` of an annotated one. The statement is
+//! re-emitted unchanged: it gets no rustdoc note under `docs`, no build warning
+//! under `warn`, and no checks under `strict` or `check-assumptions`.
 
 extern crate proc_macro;
 
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{parse_macro_input, Item, LitStr};
+use syn::{parse_macro_input, punctuated::Punctuated, Error, Expr, Item, Lit, LitStr, Meta, Token};
 
 /// A marker attribute to indicate that a function is untested.
 /// This attribute does not modify the function it annotates.
 #[proc_macro_attribute]
 pub fn untested(_attr: TokenStream, item: TokenStream) -> TokenStream {
     // Parse the input tokens into a syntax tree item (like a function).
-    let item_ast = parse_macro_input!(item as syn::Item);
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
 
-    // Use quote to reconstruct the token stream for the item.
-    // This effectively returns the original function unchanged.
-    TokenStream::from(quote! { #item_ast })
+    // Return the item unchanged (plus a build warning under the `warn` feature).
+    expand("untested", None, item_ast)
 }
 
 /// A marker attribute to indicate a specific need for an item (e.g., function).
-/// Accepts a string literal describing the need, like `#[needs("refactoring")]`.
+/// Accepts a string literal describing the need, like `#[needs("refactoring")]`,
+/// or `reason`, `issue` and `priority` keys, like
+/// `#[needs(reason = "refactoring", issue = "PROJ-12", priority = "high")]`.
 /// Can be applied multiple times to the same item.
-/// This attribute does not modify the item it annotates.
+/// The need is added to the item's documentation so it shows up in rustdoc;
+/// the item itself is not modified.
 #[proc_macro_attribute]
 pub fn needs(attr: TokenStream, item: TokenStream) -> TokenStream {
-    // Parse the attribute argument (the string literal).
-    // We expect a single string literal, e.g., #[needs("some reason")]
-    // We parse it to ensure it's a valid string literal, but don't use the value.
-    let _reason = parse_macro_input!(attr as LitStr);
+    // Parse the attribute arguments (a string literal or key-value pairs).
+    let args = parse_macro_input!(attr as NeedsArgs);
 
     // Parse the input tokens into a syntax tree item (like a function).
-    let item_ast = parse_macro_input!(item as Item);
+    let Marked(mut item_ast) = parse_macro_input!(item as Marked);
+
+    // Record the need in the item's docs, after any docs it already has.
+    if let Some(attrs) = item_attrs_mut(&mut item_ast) {
+        let doc = args.doc();
+        attrs.push(syn::parse_quote! { #[doc = ""] });
+        attrs.push(syn::parse_quote! { #[doc = #doc] });
+    }
+
+    // Return the item (plus a build warning under the `warn` feature).
+    let summary = LitStr::new(&args.summary(), proc_macro::Span::call_site().into());
+    expand("needs", Some(&summary), item_ast)
+}
+
+/// Arguments of [`macro@needs`]: a bare reason, or `reason`, `issue` and `priority` keys.
+#[derive(Default)]
+struct NeedsArgs {
+    reason: Option<String>,
+    issue: Option<String>,
+    priority: Option<String>,
+}
+
+impl NeedsArgs {
+    /// One-line summary: the reason, followed by the issue and priority when given.
+    fn summary(&self) -> String {
+        let details: Vec<String> = [("issue", &self.issue), ("priority", &self.priority)]
+            .iter()
+            .filter_map(|(key, value)| value.as_ref().map(|value| format!("{}: {}", key, value)))
+            .collect();
+
+        match (&self.reason, details.is_empty()) {
+            (Some(reason), true) => reason.clone(),
+            (Some(reason), false) => format!("{} ({})", reason, details.join(", ")),
+            (None, _) => details.join(", "),
+        }
+    }
 
-    // Use quote to reconstruct the token stream for the item.
-    // This effectively returns the original item unchanged.
-    TokenStream::from(quote! { #item_ast })
+    /// Rustdoc line describing the need.
+    fn doc(&self) -> String {
+        format!(" **Needs:** {}", self.summary())
+    }
+}
+
+impl syn::parse::Parse for NeedsArgs {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        // Legacy form: a single string literal.
+        if input.peek(LitStr) {
+            let reason: LitStr = input.parse()?;
+            return Ok(Self {
+                reason: Some(reason.value()),
+                ..Self::default()
+            });
+        }
+
+        let pairs =
+            syn::punctuated::Punctuated::<syn::MetaNameValue, syn::Token![,]>::parse_terminated(
+                input,
+            )?;
+        if pairs.is_empty() {
+            return Err(input.error(
+                "expected a string like \"refactoring\" or keys like `reason = \"refactoring\"`",
+            ));
+        }
+
+        let mut args = Self::default();
+        for pair in pairs {
+            let value = match &pair.value {
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(value),
+                    ..
+                }) => value.value(),
+                other => return Err(Error::new_spanned(other, "expected a string literal")),
+            };
+            let slot = match pair.path.get_ident() {
+                Some(key) if key == "reason" => &mut args.reason,
+                Some(key) if key == "issue" => &mut args.issue,
+                Some(key) if key == "priority" => &mut args.priority,
+                _ => {
+                    return Err(Error::new_spanned(
+                        &pair.path,
+                        "unknown key; expected `reason`, `issue` or `priority`",
+                    ))
+                },
+            };
+            if slot.replace(value).is_some() {
+                return Err(Error::new_spanned(&pair.path, "duplicate key"));
+            }
+        }
+        Ok(args)
+    }
+}
+
+/// The code a marker annotates: an item, or a statement or expression inside a
+/// function body, which is re-emitted as written in an `Item::Verbatim`.
+struct Marked(Item);
+
+impl syn::parse::Parse for Marked {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let item_error = match input.fork().parse::<Item>() {
+            Ok(_) => return input.parse().map(Self),
+            Err(err) => err,
+        };
+        let tokens = if input.fork().parse::<syn::Stmt>().is_ok() {
+            let stmt: syn::Stmt = input.parse()?;
+            quote! { #stmt }
+        } else if input.fork().parse::<Expr>().is_ok() {
+            let expr: Expr = input.parse()?;
+            quote! { #expr }
+        } else {
+            // Neither parses, so report what was wrong with it as an item.
+            return Err(item_error);
+        };
+        Ok(Self(Item::Verbatim(tokens)))
+    }
+}
+
+/// The attributes of an item, unless syn keeps it verbatim (like a bodiless trait method).
+fn item_attrs_mut(item: &mut Item) -> Option<&mut Vec<syn::Attribute>> {
+    match item {
+        Item::Const(item) => Some(&mut item.attrs),
+        Item::Enum(item) => Some(&mut item.attrs),
+        Item::ExternCrate(item) => Some(&mut item.attrs),
+        Item::Fn(item) => Some(&mut item.attrs),
+        Item::ForeignMod(item) => Some(&mut item.attrs),
+        Item::Impl(item) => Some(&mut item.attrs),
+        Item::Macro(item) => Some(&mut item.attrs),
+        Item::Mod(item) => Some(&mut item.attrs),
+        Item::Static(item) => Some(&mut item.attrs),
+        Item::Struct(item) => Some(&mut item.attrs),
+        Item::Trait(item) => Some(&mut item.attrs),
+        Item::TraitAlias(item) => Some(&mut item.attrs),
+        Item::Type(item) => Some(&mut item.attrs),
+        Item::Union(item) => Some(&mut item.attrs),
+        Item::Use(item) => Some(&mut item.attrs),
+        _ => None,
+    }
 }
 
 /// A marker attribute to indicate that a function contains `unwrap()` calls.
 /// This helps identify potential panic points in code.
+/// With the `strict` feature enabled, a function whose body (closures and nested
+/// blocks included) has no `.unwrap()` or `.expect()` call is rejected at compile time,
+/// so the marker can't outlive the unwraps it documents. Calls inside macro
+/// invocations are not seen, and items other than functions are not checked.
 /// This attribute does not modify the function it annotates.
 #[proc_macro_attribute]
 pub fn includes_unwrap(_attr: TokenStream, item: TokenStream) -> TokenStream {
     // Parse the input tokens into a syntax tree item (like a function).
-    let item_ast = parse_macro_input!(item as syn::Item);
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+
+    #[cfg(feature = "strict")]
+    if let Item::Fn(item_fn) = &item_ast {
+        if !unwrap_check::calls_unwrap(item_fn) {
+            return Error::new(
+                proc_macro::Span::call_site().into(),
+                format!(
+                    "#[includes_unwrap] on `{}`, but its body has no `.unwrap()` or `.expect()` call",
+                    item_fn.sig.ident
+                ),
+            )
+            .to_compile_error()
+            .into();
+        }
+    }
 
-    // Use quote to reconstruct the token stream for the item.
-    // This effectively returns the original function unchanged.
-    TokenStream::from(quote! { #item_ast })
+    // Return the item unchanged (plus a build warning under the `warn` feature).
+    expand("includes_unwrap", None, item_ast)
 }
 
 /// Mark code that needs performance optimization.
@@ -87,8 +326,8 @@ pub fn includes_unwrap(_attr: TokenStream, item: TokenStream) -> TokenStream {
 /// This attribute does not modify the item it annotates.
 #[proc_macro_attribute]
 pub fn perf_critical(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    let item_ast = parse_macro_input!(item as syn::Item);
-    TokenStream::from(quote! { #item_ast })
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    expand("perf_critical", None, item_ast)
 }
 
 /// Mark code with known security implications.
@@ -96,8 +335,8 @@ pub fn perf_critical(_attr: TokenStream, item: TokenStream) -> TokenStream {
 /// This attribute does not modify the item it annotates.
 #[proc_macro_attribute]
 pub fn security_sensitive(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    let item_ast = parse_macro_input!(item as syn::Item);
-    TokenStream::from(quote! { #item_ast })
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    expand("security_sensitive", None, item_ast)
 }
 
 /// Indicate code that requires special review before release.
@@ -105,8 +344,8 @@ pub fn security_sensitive(_attr: TokenStream, item: TokenStream) -> TokenStream
 /// This attribute does not modify the item it annotates.
 #[proc_macro_attribute]
 pub fn needs_review(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    let item_ast = parse_macro_input!(item as syn::Item);
-    TokenStream::from(quote! { #item_ast })
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    expand("needs_review", None, item_ast)
 }
 
 /// Mark code as temporary or intended to be replaced.
@@ -114,28 +353,70 @@ pub fn needs_review(_attr: TokenStream, item: TokenStream) -> TokenStream {
 /// This attribute does not modify the item it annotates.
 #[proc_macro_attribute]
 pub fn temporary(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    let item_ast = parse_macro_input!(item as syn::Item);
-    TokenStream::from(quote! { #item_ast })
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    expand("temporary", None, item_ast)
 }
 
 /// Indicate that code has non-obvious assumptions.
 /// Accepts a string literal describing the assumptions, like `#[assumptions("assumes sorted input")]`.
-/// This attribute does not modify the item it annotates.
+///
+/// With the `check-assumptions` feature enabled, an assumption on a function that parses as a
+/// Rust expression, like `#[assumptions("!items.is_empty()")]`, is checked by a `debug_assert!`
+/// at function entry. The expression must then compile in the function's scope. Prose
+/// assumptions are left untouched.
+/// Without the feature, this attribute does not modify the item it annotates.
 #[proc_macro_attribute]
 pub fn assumptions(attr: TokenStream, item: TokenStream) -> TokenStream {
-    let _description = parse_macro_input!(attr as LitStr);
-    let item_ast = parse_macro_input!(item as Item);
-    TokenStream::from(quote! { #item_ast })
+    let description = parse_macro_input!(attr as LitStr);
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    let item_ast = check_assumption(&description, item_ast);
+    expand("assumptions", Some(&description), item_ast)
+}
+
+/// Insert a `debug_assert!` of the assumption at function entry when it parses as an expression.
+#[cfg(feature = "check-assumptions")]
+fn check_assumption(description: &LitStr, item: Item) -> Item {
+    let mut item_fn = match item {
+        Item::Fn(item_fn) => item_fn,
+        other => return other,
+    };
+    let condition: syn::Expr = match syn::parse_str(&description.value()) {
+        Ok(condition) => condition,
+        Err(_) => return Item::Fn(item_fn),
+    };
+
+    let message = format!("assumption violated: {}", description.value());
+    item_fn.block.stmts.insert(
+        0,
+        syn::parse_quote! {
+            debug_assert!(#condition, "{}", #message);
+        },
+    );
+    Item::Fn(item_fn)
+}
+
+/// Leave the item untouched; runtime checks need the `check-assumptions` feature.
+#[cfg(not(feature = "check-assumptions"))]
+fn check_assumption(_description: &LitStr, item: Item) -> Item {
+    item
 }
 
 /// Mark code that may need revisiting in a future version.
-/// Accepts a string literal describing when to revisit, like `#[revisit_in("v2.0")]`.
+/// Accepts a string literal describing when to revisit, like `#[revisit_in("v2.0")]`
+/// or `#[revisit_in("2025-06-01")]`.
+/// With the `strict-revisit` feature enabled, anything but a version (`2`, `v2.1`,
+/// `2.1.3-beta`) or a calendar-valid `YYYY-MM-DD` date is rejected at compile time,
+/// so the scanner's `stale` check can always interpret it.
 /// This attribute does not modify the item it annotates.
 #[proc_macro_attribute]
 pub fn revisit_in(attr: TokenStream, item: TokenStream) -> TokenStream {
-    let _version = parse_macro_input!(attr as LitStr);
-    let item_ast = parse_macro_input!(item as Item);
-    TokenStream::from(quote! { #item_ast })
+    let version = parse_macro_input!(attr as LitStr);
+    #[cfg(feature = "strict-revisit")]
+    if let Err(err) = validate_revisit(&version) {
+        return err.to_compile_error().into();
+    }
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    expand("revisit_in", Some(&version), item_ast)
 }
 
 /// Mark code that's sensitive to changes in dependencies.
@@ -143,8 +424,8 @@ pub fn revisit_in(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// This attribute does not modify the item it annotates.
 #[proc_macro_attribute]
 pub fn dependency_sensitive(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    let item_ast = parse_macro_input!(item as syn::Item);
-    TokenStream::from(quote! { #item_ast })
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    expand("dependency_sensitive", None, item_ast)
 }
 
 /// Mark code that uses unsafe blocks and needs careful auditing.
@@ -153,13 +434,13 @@ pub fn dependency_sensitive(_attr: TokenStream, item: TokenStream) -> TokenStrea
 /// This attribute does not modify the item it annotates.
 #[proc_macro_attribute]
 pub fn unsafe_usage(attr: TokenStream, item: TokenStream) -> TokenStream {
-    let _reason = if !attr.is_empty() {
+    let reason = if !attr.is_empty() {
         Some(parse_macro_input!(attr as LitStr))
     } else {
         None
     };
-    let item_ast = parse_macro_input!(item as syn::Item);
-    TokenStream::from(quote! { #item_ast })
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    expand("unsafe_usage", reason.as_ref(), item_ast)
 }
 
 /// Mark code where certain clippy lints are deliberately suppressed.
@@ -168,31 +449,128 @@ pub fn unsafe_usage(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// This attribute does not modify the item it annotates.
 #[proc_macro_attribute]
 pub fn no_clippy(attr: TokenStream, item: TokenStream) -> TokenStream {
-    let _description = parse_macro_input!(attr as LitStr);
-    let item_ast = parse_macro_input!(item as Item);
-    TokenStream::from(quote! { #item_ast })
+    let description = parse_macro_input!(attr as LitStr);
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    expand("no_clippy", Some(&description), item_ast)
 }
 
-/// Indicate code with behavior tied to specific platforms.
-/// Accepts a string literal describing the platform dependencies,
+/// Restrict code to specific platforms.
+/// Accepts a string literal of comma-separated platforms,
 /// like `#[platform_specific("windows")]` or `#[platform_specific("linux, macos")]`.
-/// This attribute does not modify the item it annotates.
+/// The families `unix` and `windows` become `cfg(unix)` and `cfg(windows)`, and operating
+/// systems like `linux` or `macos` become `cfg(target_os = "...")`. Several platforms are
+/// combined with `cfg(any(...))`, and unknown platforms are rejected at compile time.
+/// Add `soft`, as in `#[platform_specific("unix", soft)]`, to only document the
+/// dependency without gating the item.
 #[proc_macro_attribute]
 pub fn platform_specific(attr: TokenStream, item: TokenStream) -> TokenStream {
-    let _platforms = parse_macro_input!(attr as LitStr);
-    let item_ast = parse_macro_input!(item as Item);
-    TokenStream::from(quote! { #item_ast })
+    let args = parse_macro_input!(attr as PlatformArgs);
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    if args.soft {
+        return expand("platform_specific", Some(&args.platforms), item_ast);
+    }
+
+    let value = args.platforms.value();
+    let mut predicates = Vec::new();
+    for platform in value.split(',').map(str::trim) {
+        let predicate = match platform {
+            "unix" => quote! { unix },
+            "windows" => quote! { windows },
+            os if TARGET_OSES.contains(&os) => quote! { target_os = #os },
+            other => {
+                return Error::new(
+                    args.platforms.span(),
+                    format!(
+                        "unknown platform \"{}\"; expected unix, windows or one of: {}",
+                        other,
+                        TARGET_OSES.join(", ")
+                    ),
+                )
+                .to_compile_error()
+                .into();
+            },
+        };
+        predicates.push(predicate);
+    }
+
+    let mut gated = TokenStream::from(quote! { #[cfg(any(#(#predicates),*))] });
+    gated.extend(expand("platform_specific", Some(&args.platforms), item_ast));
+    gated
 }
 
-/// Mark code dependent on specific feature flags.
-/// Accepts a string literal describing the feature dependency,
-/// like `#[feature_gated("async")]` or `#[feature_gated("extended-api")]`.
-/// This attribute does not modify the item it annotates.
+/// Operating systems accepted by [`macro@platform_specific`] as `target_os` values.
+const TARGET_OSES: &[&str] = &[
+    "linux",
+    "macos",
+    "ios",
+    "android",
+    "freebsd",
+    "netbsd",
+    "openbsd",
+    "dragonfly",
+    "solaris",
+    "illumos",
+    "fuchsia",
+    "redox",
+    "wasi",
+];
+
+/// Arguments of [`macro@platform_specific`]: the platforms, optionally followed by `soft`.
+struct PlatformArgs {
+    platforms: LitStr,
+    soft: bool,
+}
+
+impl syn::parse::Parse for PlatformArgs {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let platforms = input.parse()?;
+        if input.is_empty() {
+            return Ok(Self {
+                platforms,
+                soft: false,
+            });
+        }
+
+        input.parse::<syn::Token![,]>()?;
+        let flag: syn::Ident = input.parse()?;
+        if flag != "soft" {
+            return Err(Error::new(flag.span(), "expected `soft`"));
+        }
+        Ok(Self {
+            platforms,
+            soft: true,
+        })
+    }
+}
+
+/// Gate code behind one or more feature flags.
+/// Accepts a string literal of comma-separated feature names,
+/// like `#[feature_gated("async")]` or `#[feature_gated("extended-api, unstable")]`.
+/// The item is wrapped in `#[cfg(any(feature = "..."))]`, so it is only compiled
+/// when at least one of the features is enabled. Other attributes are kept.
 #[proc_macro_attribute]
 pub fn feature_gated(attr: TokenStream, item: TokenStream) -> TokenStream {
-    let _feature = parse_macro_input!(attr as LitStr);
-    let item_ast = parse_macro_input!(item as Item);
-    TokenStream::from(quote! { #item_ast })
+    let features = parse_macro_input!(attr as LitStr);
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+
+    let value = features.value();
+    let names: Vec<&str> = value
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .collect();
+    if names.is_empty() {
+        return Error::new(
+            features.span(),
+            "expected at least one feature name, like \"advanced-features\"",
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    let mut gated = TokenStream::from(quote! { #[cfg(any(#(feature = #names),*))] });
+    gated.extend(expand("feature_gated", Some(&features), item_ast));
+    gated
 }
 
 /// Indicate algorithm complexity or cognitive complexity issues.
@@ -201,9 +579,9 @@ pub fn feature_gated(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// This attribute does not modify the item it annotates.
 #[proc_macro_attribute]
 pub fn complexity(attr: TokenStream, item: TokenStream) -> TokenStream {
-    let _description = parse_macro_input!(attr as LitStr);
-    let item_ast = parse_macro_input!(item as Item);
-    TokenStream::from(quote! { #item_ast })
+    let description = parse_macro_input!(attr as LitStr);
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    expand("complexity", Some(&description), item_ast)
 }
 
 /// Flag functions that perform significant heap allocations.
@@ -212,13 +590,13 @@ pub fn complexity(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// This attribute does not modify the item it annotates.
 #[proc_macro_attribute]
 pub fn allocation_heavy(attr: TokenStream, item: TokenStream) -> TokenStream {
-    let _details = if !attr.is_empty() {
+    let details = if !attr.is_empty() {
         Some(parse_macro_input!(attr as LitStr))
     } else {
         None
     };
-    let item_ast = parse_macro_input!(item as syn::Item);
-    TokenStream::from(quote! { #item_ast })
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    expand("allocation_heavy", details.as_ref(), item_ast)
 }
 
 /// Highlight code paths that might panic under specific conditions.
@@ -227,9 +605,9 @@ pub fn allocation_heavy(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// This attribute does not modify the item it annotates.
 #[proc_macro_attribute]
 pub fn panic_path(attr: TokenStream, item: TokenStream) -> TokenStream {
-    let _scenario = parse_macro_input!(attr as LitStr);
-    let item_ast = parse_macro_input!(item as Item);
-    TokenStream::from(quote! { #item_ast })
+    let scenario = parse_macro_input!(attr as LitStr);
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    expand("panic_path", Some(&scenario), item_ast)
 }
 
 /// Indicate parts of the API that may change.
@@ -239,9 +617,9 @@ pub fn panic_path(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// This attribute does not modify the item it annotates.
 #[proc_macro_attribute]
 pub fn api_stability(attr: TokenStream, item: TokenStream) -> TokenStream {
-    let _stability = parse_macro_input!(attr as LitStr);
-    let item_ast = parse_macro_input!(item as Item);
-    TokenStream::from(quote! { #item_ast })
+    let stability = parse_macro_input!(attr as LitStr);
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    expand("api_stability", Some(&stability), item_ast)
 }
 
 /// Mark code with potential concurrency/deadlock issues.
@@ -250,13 +628,13 @@ pub fn api_stability(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// This attribute does not modify the item it annotates.
 #[proc_macro_attribute]
 pub fn deadlock_risk(attr: TokenStream, item: TokenStream) -> TokenStream {
-    let _details = if !attr.is_empty() {
+    let details = if !attr.is_empty() {
         Some(parse_macro_input!(attr as LitStr))
     } else {
         None
     };
-    let item_ast = parse_macro_input!(item as syn::Item);
-    TokenStream::from(quote! { #item_ast })
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    expand("deadlock_risk", details.as_ref(), item_ast)
 }
 
 /// Flag code that should be benchmarked and optimized.
@@ -265,11 +643,1104 @@ pub fn deadlock_risk(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// This attribute does not modify the item it annotates.
 #[proc_macro_attribute]
 pub fn benchmark_candidate(attr: TokenStream, item: TokenStream) -> TokenStream {
-    let _notes = if !attr.is_empty() {
+    let notes = if !attr.is_empty() {
         Some(parse_macro_input!(attr as LitStr))
     } else {
         None
     };
-    let item_ast = parse_macro_input!(item as syn::Item);
-    TokenStream::from(quote! { #item_ast })
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    expand("benchmark_candidate", notes.as_ref(), item_ast)
+}
+
+/// Flag code that handles untrusted input and should be covered by a fuzz target.
+/// Accepts a string literal describing what should be fuzzed,
+/// like `#[requires_fuzzing("parser input")]`.
+/// This attribute does not modify the item it annotates.
+#[proc_macro_attribute]
+pub fn requires_fuzzing(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let description = parse_macro_input!(attr as LitStr);
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    expand("requires_fuzzing", Some(&description), item_ast)
+}
+
+/// Mark code whose resource cost scales with a deployment dimension.
+/// Accepts a string literal describing the scaling dimension,
+/// like `#[requires_capacity_planning("scales with tenants")]`.
+/// This attribute does not modify the item it annotates.
+#[proc_macro_attribute]
+pub fn requires_capacity_planning(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let dimension = parse_macro_input!(attr as LitStr);
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    expand("requires_capacity_planning", Some(&dimension), item_ast)
+}
+
+/// Mark code belonging to a feature that is scheduled for removal.
+/// Accepts an ISO-8601 date (`YYYY-MM-DD`) for the planned sunset,
+/// like `#[requires_feature_sunset("2025-01-01")]`.
+/// Malformed dates are rejected at compile time.
+/// This attribute does not modify the item it annotates.
+#[proc_macro_attribute]
+pub fn requires_feature_sunset(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let date = parse_macro_input!(attr as LitStr);
+    if let Err(err) = validate_iso_date(&date) {
+        return err.to_compile_error().into();
+    }
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    expand("requires_feature_sunset", Some(&date), item_ast)
+}
+
+/// Mark code that depends on a migration that has not been carried out yet.
+/// Accepts a string literal describing the migration,
+/// like `#[requires_migration("removes compat shim")]`.
+/// Pair it with [`macro@migration_complete`] using the same description once done.
+/// This attribute does not modify the item it annotates.
+#[proc_macro_attribute]
+pub fn requires_migration(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let description = parse_macro_input!(attr as LitStr);
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    expand("requires_migration", Some(&description), item_ast)
+}
+
+/// Record that a migration required elsewhere has been completed.
+/// Accepts a string literal matching the description used in `requires_migration`,
+/// like `#[migration_complete("removes compat shim")]`.
+/// This attribute does not modify the item it annotates.
+#[proc_macro_attribute]
+pub fn migration_complete(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let description = parse_macro_input!(attr as LitStr);
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    expand("migration_complete", Some(&description), item_ast)
+}
+
+/// Mark ported code that has not reached parity with the implementation it replaces.
+/// Accepts a string literal naming the reference behaviour,
+/// like `#[requires_feature_parity("matches python impl")]`.
+/// This attribute does not modify the item it annotates.
+#[proc_macro_attribute]
+pub fn requires_feature_parity(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let reference = parse_macro_input!(attr as LitStr);
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    expand("requires_feature_parity", Some(&reference), item_ast)
+}
+
+/// Mark a backward-compatibility shim kept alive for older clients.
+/// Accepts a string literal describing what is still supported,
+/// like `#[legacy_compat("pre-2.0 clients")]`.
+/// This attribute does not modify the item it annotates.
+#[proc_macro_attribute]
+pub fn legacy_compat(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let supported = parse_macro_input!(attr as LitStr);
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    expand("legacy_compat", Some(&supported), item_ast)
+}
+
+/// Record the release an item was deprecated in.
+/// Accepts a string literal naming the version, like `#[deprecated_since("1.4.0")]`.
+/// This attribute does not modify the item it annotates.
+#[proc_macro_attribute]
+pub fn deprecated_since(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let version = parse_macro_input!(attr as LitStr);
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    expand("deprecated_since", Some(&version), item_ast)
+}
+
+/// Link new code to the item it supersedes.
+/// Accepts a string literal naming the replaced item,
+/// like `#[replaces("old_function")]`.
+/// The replaced item is expected to be removed once callers have moved over.
+/// This attribute does not modify the item it annotates.
+#[proc_macro_attribute]
+pub fn replaces(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let replaced = parse_macro_input!(attr as LitStr);
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    expand("replaces", Some(&replaced), item_ast)
+}
+
+/// Flag locale-sensitive code that needs internationalization review.
+/// Accepts a string literal describing the locale concern,
+/// like `#[requires_locale("handles RTL")]`.
+/// This attribute does not modify the item it annotates.
+#[proc_macro_attribute]
+pub fn requires_locale(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let concern = parse_macro_input!(attr as LitStr);
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    expand("requires_locale", Some(&concern), item_ast)
+}
+
+/// Mark a function that must run inside a database transaction.
+/// Accepts a string literal with a note about the requirement,
+/// like `#[requires_transaction("must run in db tx")]`.
+/// This attribute does not modify the item it annotates.
+#[proc_macro_attribute]
+pub fn requires_transaction(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let note = parse_macro_input!(attr as LitStr);
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    expand("requires_transaction", Some(&note), item_ast)
+}
+
+/// Mark code that must clean up or roll back partial work when it fails.
+/// Accepts a string literal describing the required cleanup,
+/// like `#[requires_cleanup_on_error("rollback partial writes")]`.
+/// This attribute does not modify the item it annotates.
+#[proc_macro_attribute]
+pub fn requires_cleanup_on_error(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let cleanup = parse_macro_input!(attr as LitStr);
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    expand("requires_cleanup_on_error", Some(&cleanup), item_ast)
+}
+
+/// Mark numerical code that trades accuracy for speed.
+/// Accepts a string literal describing the approximation,
+/// like `#[approximation("uses fast inverse sqrt")]`.
+/// This attribute does not modify the item it annotates.
+#[proc_macro_attribute]
+pub fn approximation(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let description = parse_macro_input!(attr as LitStr);
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    expand("approximation", Some(&description), item_ast)
+}
+
+/// Mark data shared across threads without proper synchronization.
+/// Distinct from [`macro@deadlock_risk`]: this flags unsynchronized access rather than lock ordering.
+/// Accepts a string literal describing the hazard,
+/// like `#[data_race_risk("shared without sync")]`.
+/// This attribute does not modify the item it annotates.
+#[proc_macro_attribute]
+pub fn data_race_risk(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let description = parse_macro_input!(attr as LitStr);
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    expand("data_race_risk", Some(&description), item_ast)
+}
+
+/// Mark code that relies on operations happening in a fixed order across threads.
+/// Accepts a string literal stating the order,
+/// like `#[requires_ordering("lock accounts before the ledger")]`.
+/// This attribute does not modify the item it annotates.
+#[proc_macro_attribute]
+pub fn requires_ordering(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let order = parse_macro_input!(attr as LitStr);
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    expand("requires_ordering", Some(&order), item_ast)
+}
+
+/// Mark an authorization checkpoint and the permission it requires.
+/// Accepts a string literal naming the required permission,
+/// like `#[requires_permission_check("admin only")]`.
+/// This attribute does not modify the item it annotates.
+#[proc_macro_attribute]
+pub fn requires_permission_check(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let permission = parse_macro_input!(attr as LitStr);
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    expand("requires_permission_check", Some(&permission), item_ast)
+}
+
+/// Flag behavior that needs a test proving older clients keep working.
+/// Accepts a string literal describing the clients or versions to cover,
+/// like `#[requires_backward_compat_test("v1 clients")]`.
+/// This attribute does not modify the item it annotates.
+#[proc_macro_attribute]
+pub fn requires_backward_compat_test(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let clients = parse_macro_input!(attr as LitStr);
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    expand("requires_backward_compat_test", Some(&clients), item_ast)
+}
+
+/// Flag a schema change that needs a test running its migration against existing data.
+/// Accepts a string literal naming the migration or data to cover,
+/// like `#[requires_schema_migration_test("users.email becomes unique")]`.
+/// This attribute does not modify the item it annotates.
+#[proc_macro_attribute]
+pub fn requires_schema_migration_test(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let migration = parse_macro_input!(attr as LitStr);
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    expand("requires_schema_migration_test", Some(&migration), item_ast)
+}
+
+/// Record a secret the code needs at runtime.
+/// Accepts a string literal naming the secret as it appears in the secret store,
+/// like `#[requires_secret("STRIPE_API_KEY")]`.
+/// This attribute does not modify the item it annotates.
+#[proc_macro_attribute]
+pub fn requires_secret(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let secret = parse_macro_input!(attr as LitStr);
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    expand("requires_secret", Some(&secret), item_ast)
+}
+
+/// Set a hard deadline by which code must be reviewed.
+/// Stronger than [`macro@needs_review`]: accepts an ISO-8601 date (`YYYY-MM-DD`),
+/// like `#[requires_review_before("2024-07-01")]`, that CI can enforce.
+/// Malformed dates are rejected at compile time.
+/// This attribute does not modify the item it annotates.
+#[proc_macro_attribute]
+pub fn requires_review_before(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let date = parse_macro_input!(attr as LitStr);
+    if let Err(err) = validate_iso_date(&date) {
+        return err.to_compile_error().into();
+    }
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    expand("requires_review_before", Some(&date), item_ast)
+}
+
+/// Document a deliberate choice of numeric precision.
+/// Accepts a string literal explaining the choice, like `#[numeric_precision("f32 sufficient")]`.
+/// This attribute does not modify the item it annotates.
+#[proc_macro_attribute]
+pub fn numeric_precision(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let note = parse_macro_input!(attr as LitStr);
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    expand("numeric_precision", Some(&note), item_ast)
+}
+
+/// Mark a component that must shut down gracefully.
+/// Accepts a string literal describing the shutdown handling needed,
+/// like `#[requires_graceful_shutdown("drain connections")]`.
+/// This attribute does not modify the item it annotates.
+#[proc_macro_attribute]
+pub fn requires_graceful_shutdown(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let handling = parse_macro_input!(attr as LitStr);
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    expand("requires_graceful_shutdown", Some(&handling), item_ast)
+}
+
+/// Mark calls to a downstream dependency that need a circuit breaker.
+/// Accepts a string literal naming the dependency,
+/// like `#[requires_circuit_breaker("payments-api")]`.
+/// This attribute does not modify the item it annotates.
+#[proc_macro_attribute]
+pub fn requires_circuit_breaker(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let dependency = parse_macro_input!(attr as LitStr);
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    expand("requires_circuit_breaker", Some(&dependency), item_ast)
+}
+
+/// Flag a path that needs a load test before a milestone.
+/// Accepts a string literal naming the milestone, like `#[requires_load_test("before GA")]`.
+/// This attribute does not modify the item it annotates.
+#[proc_macro_attribute]
+pub fn requires_load_test(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let milestone = parse_macro_input!(attr as LitStr);
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    expand("requires_load_test", Some(&milestone), item_ast)
+}
+
+/// Flag UI code that needs an accessibility review.
+/// Accepts a string literal describing the concern,
+/// like `#[requires_accessibility_review("screen reader")]`.
+/// This attribute does not modify the item it annotates.
+#[proc_macro_attribute]
+pub fn requires_accessibility_review(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let concern = parse_macro_input!(attr as LitStr);
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    expand("requires_accessibility_review", Some(&concern), item_ast)
+}
+
+/// Record that the concern markers on an item have been reviewed.
+/// Accepts a string literal of the form `"<YYYY-MM-DD> by <reviewer>"`,
+/// like `#[reviewed("2024-03-01 by alice")]`.
+/// Malformed values are rejected at compile time.
+/// This attribute does not modify the item it annotates.
+#[proc_macro_attribute]
+pub fn reviewed(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let review = parse_macro_input!(attr as LitStr);
+    if let Err(err) = validate_review(&review) {
+        return err.to_compile_error().into();
+    }
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    expand("reviewed", Some(&review), item_ast)
+}
+
+/// Flag code that needs a chaos experiment for a failure scenario.
+/// Accepts a string literal naming the scenario, like `#[requires_chaos_test("node failure")]`.
+/// This attribute does not modify the item it annotates.
+#[proc_macro_attribute]
+pub fn requires_chaos_test(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let scenario = parse_macro_input!(attr as LitStr);
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    expand("requires_chaos_test", Some(&scenario), item_ast)
+}
+
+/// Apply several markers with one attribute.
+/// Accepts a comma-separated list of marker names, each bare or given a string
+/// argument with `=`, like `#[status(untested, needs = "hashing", complexity = "O(n)")]`.
+/// Each entry behaves exactly like the marker written as its own attribute, so
+/// `needs = "hashing"` is `#[needs("hashing")]`, argument checks included.
+/// Names that aren't code-status markers are rejected at compile time.
+/// The markers are expanded as `::code_status_macros::<name>`, so the crate must
+/// not be renamed in `Cargo.toml`.
+#[proc_macro_attribute]
+pub fn status(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let entries = parse_macro_input!(attr with Punctuated::<Meta, Token![,]>::parse_terminated);
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+
+    let mut markers = Vec::new();
+    for entry in &entries {
+        let (path, argument) = match entry {
+            Meta::Path(path) => (path, None),
+            Meta::NameValue(name_value) => match &name_value.value {
+                Expr::Lit(syn::ExprLit {
+                    lit: Lit::Str(argument),
+                    ..
+                }) => (&name_value.path, Some(argument)),
+                other => {
+                    return Error::new_spanned(other, "expected a string literal")
+                        .to_compile_error()
+                        .into();
+                },
+            },
+            Meta::List(list) => {
+                return Error::new_spanned(
+                    list,
+                    "write a marker's argument as `name = \"...\"` inside #[status]",
+                )
+                .to_compile_error()
+                .into();
+            },
+        };
+
+        let marker = match path.get_ident() {
+            Some(marker)
+                if code_status_core::MACRO_NAMES.contains(&marker.to_string().as_str()) =>
+            {
+                marker
+            },
+            _ => {
+                return Error::new_spanned(path, "not a code-status marker")
+                    .to_compile_error()
+                    .into();
+            },
+        };
+        markers.push(match argument {
+            Some(argument) => quote::quote_spanned! {marker.span()=>
+                #[::code_status_macros::#marker(#argument)]
+            },
+            None => quote::quote_spanned! {marker.span()=>
+                #[::code_status_macros::#marker]
+            },
+        });
+    }
+
+    TokenStream::from(quote! {
+        #(#markers)*
+        #item_ast
+    })
+}
+
+/// Body inspection for the `strict` form of [`macro@includes_unwrap`].
+#[cfg(feature = "strict")]
+mod unwrap_check {
+    use syn::visit::{self, Visit};
+
+    /// Whether the function body calls `.unwrap()` or `.expect()` anywhere,
+    /// including inside closures and nested blocks.
+    pub fn calls_unwrap(item_fn: &syn::ItemFn) -> bool {
+        let mut finder = UnwrapFinder { found: false };
+        finder.visit_block(&item_fn.block);
+        finder.found
+    }
+
+    struct UnwrapFinder {
+        found: bool,
+    }
+
+    impl<'ast> Visit<'ast> for UnwrapFinder {
+        fn visit_expr_method_call(&mut self, call: &'ast syn::ExprMethodCall) {
+            if call.method == "unwrap" || call.method == "expect" {
+                self.found = true;
+                return;
+            }
+            visit::visit_expr_method_call(self, call);
+        }
+    }
+}
+
+/// Type check for the `strict` form of [`macro@not_thread_safe`].
+#[cfg(feature = "strict")]
+mod send_check {
+    use proc_macro::TokenStream;
+    use quote::quote;
+    use syn::Item;
+
+    /// An assertion that a non-generic struct, enum or union is not `Send`, or
+    /// nothing for other items.
+    ///
+    /// Rust can't bound on `!Send`, so the assertion makes a path ambiguous
+    /// instead: it resolves through a blanket impl alone unless the type is
+    /// also `Send`, when a second impl applies and inference fails.
+    pub fn assert_not_send(item: &Item) -> Option<TokenStream> {
+        let (ident, generics) = match item {
+            Item::Struct(item) => (&item.ident, &item.generics),
+            Item::Enum(item) => (&item.ident, &item.generics),
+            Item::Union(item) => (&item.ident, &item.generics),
+            _ => return None,
+        };
+        if !generics.params.is_empty() {
+            return None;
+        }
+
+        Some(TokenStream::from(quote! {
+            const _: fn() = || {
+                trait NotThreadSafeButSend<A> {
+                    fn check() {}
+                }
+                impl<T: ?Sized> NotThreadSafeButSend<()> for T {}
+                struct IsSend;
+                impl<T: ?Sized + Send> NotThreadSafeButSend<IsSend> for T {}
+                let _ = <#ident as NotThreadSafeButSend<_>>::check;
+            };
+        }))
+    }
+}
+
+/// Re-emit an annotated item unchanged (apart from its status note under the `docs` feature).
+#[cfg(not(feature = "warn"))]
+fn expand(marker: &str, argument: Option<&LitStr>, item: Item) -> TokenStream {
+    assert_registered(marker);
+    let item = document(marker, argument, item);
+    TokenStream::from(quote! { #item })
+}
+
+/// Prefix of the rustdoc notes added by [`document`].
+#[cfg(feature = "docs")]
+const DOC_NOTE_PREFIX: &str = " ⚠ **";
+
+/// Put a rustdoc note naming the marker and its argument ahead of the item's docs.
+///
+/// Each note is a paragraph of its own, in the order the markers are written, and
+/// a marker applied twice with the same argument is noted once. `needs` already
+/// documents itself, so it gets no note.
+#[cfg(feature = "docs")]
+fn document(marker: &str, argument: Option<&LitStr>, mut item: Item) -> Item {
+    if marker == "needs" {
+        return item;
+    }
+    let attrs = match item_attrs_mut(&mut item) {
+        Some(attrs) => attrs,
+        None => return item,
+    };
+
+    let note = match argument.map(LitStr::value) {
+        Some(argument) if !argument.is_empty() => {
+            format!("{}{}:** {}", DOC_NOTE_PREFIX, marker, argument)
+        },
+        _ => format!("{}{}**", DOC_NOTE_PREFIX, marker),
+    };
+
+    // Notes from markers written above this one, each followed by a blank line.
+    let mut end = 0;
+    while let Some(doc) = attrs.get(end).and_then(doc_value) {
+        if doc == note {
+            return item;
+        }
+        if !doc.starts_with(DOC_NOTE_PREFIX) {
+            break;
+        }
+        end += 2;
+    }
+
+    attrs.insert(end, syn::parse_quote! { #[doc = #note] });
+    attrs.insert(end + 1, syn::parse_quote! { #[doc = ""] });
+    item
+}
+
+/// Leave the item's docs untouched; status notes need the `docs` feature.
+#[cfg(not(feature = "docs"))]
+fn document(_marker: &str, _argument: Option<&LitStr>, item: Item) -> Item {
+    item
+}
+
+/// The text of a `#[doc = "..."]` attribute (which `///` comments expand to).
+#[cfg(feature = "docs")]
+fn doc_value(attr: &syn::Attribute) -> Option<String> {
+    match &attr.meta {
+        Meta::NameValue(syn::MetaNameValue {
+            path,
+            value: Expr::Lit(syn::ExprLit {
+                lit: Lit::Str(doc), ..
+            }),
+            ..
+        }) if path.is_ident("doc") => Some(doc.value()),
+        _ => None,
+    }
+}
+
+/// Panic (failing the build at the marker's first use) if `marker` is missing from
+/// `code_status_core::MACRO_NAMES`, which the scanner relies on to find it.
+fn assert_registered(marker: &str) {
+    assert!(
+        code_status_core::MACRO_NAMES.contains(&marker),
+        "`{}` is not registered in code_status_core::MACRO_NAMES",
+        marker
+    );
+}
+
+/// Re-emit an annotated item along with a build warning naming the marker.
+///
+/// Stable Rust has no API for custom warnings, so the warning comes from calling a
+/// `#[deprecated]` function whose note is the marker and its argument. Functions get
+/// the call inside their body, which also works for methods; other items get it in a
+/// sibling `const _`. Associated consts and types, and bodiless trait methods, are
+/// left without a warning since no sibling item may follow them.
+#[cfg(feature = "warn")]
+fn expand(marker: &str, argument: Option<&LitStr>, item: Item) -> TokenStream {
+    assert_registered(marker);
+    let item = document(marker, argument, item);
+    let note = match argument {
+        Some(argument) => format!("{}: {}", marker, argument.value()),
+        None => marker.to_string(),
+    };
+    let warning: syn::ItemConst = syn::parse_quote! {
+        const _: () = {
+            #[deprecated(note = #note)]
+            const fn code_status_marker() {}
+            code_status_marker();
+        };
+    };
+
+    match item {
+        Item::Fn(mut item_fn) => {
+            item_fn
+                .block
+                .stmts
+                .insert(0, syn::Stmt::Item(Item::Const(warning)));
+            TokenStream::from(quote! { #item_fn })
+        },
+        Item::Const(_) | Item::Type(_) | Item::Verbatim(_) => TokenStream::from(quote! { #item }),
+        item => TokenStream::from(quote! { #item #warning }),
+    }
+}
+
+/// Flag public documentation or examples that need review.
+/// Accepts a string literal giving the context, like `#[requires_documentation_review("public example")]`.
+/// This attribute does not modify the item it annotates.
+#[proc_macro_attribute]
+pub fn requires_documentation_review(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let context = parse_macro_input!(attr as LitStr);
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    expand("requires_documentation_review", Some(&context), item_ast)
+}
+
+/// Flag a key path that needs metrics.
+/// Accepts a string literal describing the metric, like `#[requires_metrics("latency histogram")]`.
+/// This attribute does not modify the item it annotates.
+#[proc_macro_attribute]
+pub fn requires_metrics(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let metric = parse_macro_input!(attr as LitStr);
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    expand("requires_metrics", Some(&metric), item_ast)
+}
+
+/// Record the metric or trace that already instruments a path.
+/// Accepts a string literal naming it, like `#[observed("http_request_duration_seconds")]`.
+/// This attribute does not modify the item it annotates.
+#[proc_macro_attribute]
+pub fn observed(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let instrument = parse_macro_input!(attr as LitStr);
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    expand("observed", Some(&instrument), item_ast)
+}
+
+/// Flag a change that needs a benchmark run before it merges.
+/// Accepts a string literal naming the benchmark,
+/// like `#[needs_benchmark_before_merge("parse throughput")]`.
+/// Unresolved markers count against the scanner's `release-readiness` score.
+/// This attribute does not modify the item it annotates.
+#[proc_macro_attribute]
+pub fn needs_benchmark_before_merge(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let benchmark = parse_macro_input!(attr as LitStr);
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    expand("needs_benchmark_before_merge", Some(&benchmark), item_ast)
+}
+
+/// Mark code handling untrusted input that must be sanitized against an injection class.
+/// Accepts one of `SQL`, `shell`, `HTML`, `path` or `log` (case-insensitive),
+/// like `#[requires_input_sanitization("SQL")]`.
+/// Other values are rejected at compile time.
+/// This attribute does not modify the item it annotates.
+#[proc_macro_attribute]
+pub fn requires_input_sanitization(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let class = parse_macro_input!(attr as LitStr);
+    if !INJECTION_CLASSES
+        .iter()
+        .any(|known| known.eq_ignore_ascii_case(class.value().trim()))
+    {
+        return Error::new(
+            class.span(),
+            format!(
+                "unknown injection class \"{}\"; expected one of: {}",
+                class.value(),
+                INJECTION_CLASSES.join(", ")
+            ),
+        )
+        .to_compile_error()
+        .into();
+    }
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    expand("requires_input_sanitization", Some(&class), item_ast)
+}
+
+/// Injection classes accepted by [`macro@requires_input_sanitization`].
+const INJECTION_CLASSES: &[&str] = &["SQL", "shell", "HTML", "path", "log"];
+
+/// Flag a user-facing feature that needs release notes.
+/// Accepts a string literal with a note, such as the ticket or wording to use,
+/// like `#[requires_feature_documentation("changelog entry")]`.
+/// This attribute does not modify the item it annotates.
+#[proc_macro_attribute]
+pub fn requires_feature_documentation(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let note = parse_macro_input!(attr as LitStr);
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    expand("requires_feature_documentation", Some(&note), item_ast)
+}
+
+/// Flag code that needs a property-based test, such as a serializer or parser.
+/// Accepts a string literal describing the property to check,
+/// like `#[requires_property_test("roundtrip")]`.
+/// This attribute does not modify the item it annotates.
+#[proc_macro_attribute]
+pub fn requires_property_test(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let property = parse_macro_input!(attr as LitStr);
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    expand("requires_property_test", Some(&property), item_ast)
+}
+
+/// Record how many tests an item still needs.
+/// Accepts the number of tests wanted, like `#[needs_tests(5)]`, or `have` and
+/// `want` counts, like `#[needs_tests(have = 2, want = 5)]`; `have` defaults to 0.
+/// A `have` greater than `want` is rejected at compile time.
+/// The scanner adds up the missing tests as a burn-down metric in `summary`.
+/// This attribute does not modify the item it annotates.
+#[proc_macro_attribute]
+pub fn needs_tests(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(attr as NeedsTestsArgs);
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    let summary = LitStr::new(
+        &format!("{} of {} tests", args.have, args.want),
+        proc_macro::Span::call_site().into(),
+    );
+    expand("needs_tests", Some(&summary), item_ast)
+}
+
+/// Arguments of [`macro@needs_tests`]: a bare wanted count, or `have` and `want` keys.
+struct NeedsTestsArgs {
+    have: u64,
+    want: u64,
+}
+
+impl syn::parse::Parse for NeedsTestsArgs {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        // Short form: just the number of tests wanted.
+        if input.peek(syn::LitInt) {
+            let want: syn::LitInt = input.parse()?;
+            return Ok(Self {
+                have: 0,
+                want: want.base10_parse()?,
+            });
+        }
+
+        if input.peek(LitStr) {
+            return Err(input.error("expected a count like `5`, without quotes"));
+        }
+
+        let pairs = Punctuated::<syn::MetaNameValue, Token![,]>::parse_terminated(input)?;
+        let mut have = None;
+        let mut want = None;
+        for pair in &pairs {
+            let value: u64 = match &pair.value {
+                Expr::Lit(syn::ExprLit {
+                    lit: Lit::Int(value),
+                    ..
+                }) => value.base10_parse()?,
+                other => return Err(Error::new_spanned(other, "expected an integer literal")),
+            };
+            let slot = match pair.path.get_ident() {
+                Some(key) if key == "have" => &mut have,
+                Some(key) if key == "want" => &mut want,
+                _ => {
+                    return Err(Error::new_spanned(
+                        &pair.path,
+                        "unknown key; expected `have` or `want`",
+                    ))
+                },
+            };
+            if slot.replace((value, pair)).is_some() {
+                return Err(Error::new_spanned(&pair.path, "duplicate key"));
+            }
+        }
+
+        let want = match want {
+            Some((want, _)) => want,
+            None if pairs.is_empty() => {
+                return Err(
+                    input.error("expected a count like `5` or keys like `have = 2, want = 5`")
+                )
+            },
+            None => return Err(Error::new_spanned(&pairs, "missing `want`")),
+        };
+        let have = match have {
+            Some((have, pair)) if have > want => {
+                return Err(Error::new_spanned(
+                    pair,
+                    format!("`have` ({}) is greater than `want` ({})", have, want),
+                ))
+            },
+            Some((have, _)) => have,
+            None => 0,
+        };
+        Ok(Self { have, want })
+    }
+}
+
+/// Name the team or person responsible for an item.
+/// Accepts a string literal, like `#[owner("payments-team")]`.
+/// Apply it more than once for shared ownership; the scanner's `owners` report
+/// attributes the item's other markers to each owner.
+/// This attribute does not modify the item it annotates.
+#[proc_macro_attribute]
+pub fn owner(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let owner = parse_macro_input!(attr as LitStr);
+    if owner.value().trim().is_empty() {
+        return Error::new(owner.span(), "owner must not be empty")
+            .to_compile_error()
+            .into();
+    }
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    expand("owner", Some(&owner), item_ast)
+}
+
+/// A marker attribute for a function that tests don't exercise, as a coverage
+/// report should confirm. The scanner's `coverage` subcommand checks it against
+/// an LCOV file and flags the marker once the function is covered.
+/// This attribute does not modify the function it annotates.
+#[proc_macro_attribute]
+pub fn coverage_gap(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+
+    expand("coverage_gap", None, item_ast)
+}
+
+/// Record an intentional breaking change to the public API.
+/// Accepts a string literal describing the change, like `#[breaking_change("removed field X")]`.
+/// An empty description is rejected at compile time. The scanner's `breaking-changes`
+/// subcommand lists the markers added since a git tag, to justify a major version bump.
+/// This attribute does not modify the item it annotates.
+#[proc_macro_attribute]
+pub fn breaking_change(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let change = parse_macro_input!(attr as LitStr);
+    if change.value().trim().is_empty() {
+        return Error::new(change.span(), "describe the breaking change")
+            .to_compile_error()
+            .into();
+    }
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    expand("breaking_change", Some(&change), item_ast)
+}
+
+/// Flag a change that must not ship without a plan for rolling it back.
+/// Accepts a string literal describing what a rollback involves,
+/// like `#[requires_rollback_plan("restore the old index")]`.
+/// Unresolved markers count against the scanner's `release-readiness` score.
+/// This attribute does not modify the item it annotates.
+#[proc_macro_attribute]
+pub fn requires_rollback_plan(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let plan = parse_macro_input!(attr as LitStr);
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+    expand("requires_rollback_plan", Some(&plan), item_ast)
+}
+
+/// Mark blocking I/O, such as a synchronous file read, that stalls the executor when
+/// it runs inside async code.
+/// Accepts a string literal describing the blocking call, like `#[blocking_io("reads file synchronously")]`.
+/// With the `strict` feature enabled, a function that isn't an `async fn` is rejected
+/// at compile time, since the marker only matters in async code.
+/// This attribute does not modify the item it annotates.
+#[proc_macro_attribute]
+pub fn blocking_io(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let description = parse_macro_input!(attr as LitStr);
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+
+    #[cfg(feature = "strict")]
+    if let Item::Fn(item_fn) = &item_ast {
+        if item_fn.sig.asyncness.is_none() {
+            return Error::new_spanned(
+                item_fn.sig.fn_token,
+                format!(
+                    "#[blocking_io] on `{}`, which is not an `async fn`",
+                    item_fn.sig.ident
+                ),
+            )
+            .to_compile_error()
+            .into();
+        }
+    }
+
+    expand("blocking_io", Some(&description), item_ast)
+}
+
+/// Mark a type that must never cross threads, such as one relying on `Rc` or `Cell`.
+/// Accepts a string literal saying why, like `#[not_thread_safe("uses Rc internally")]`.
+/// With the `strict` feature enabled, a non-generic struct, enum or union that is
+/// `Send` fails to compile, so the marker can't outlive the reason for it. The error
+/// is a "type annotations needed" naming `NotThreadSafeButSend`; generic types are not checked.
+/// This attribute does not modify the item it annotates.
+#[proc_macro_attribute]
+pub fn not_thread_safe(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let reason = parse_macro_input!(attr as LitStr);
+    let Marked(item_ast) = parse_macro_input!(item as Marked);
+
+    #[cfg(feature = "strict")]
+    let assertion = send_check::assert_not_send(&item_ast);
+
+    #[allow(unused_mut)]
+    let mut expanded = expand("not_thread_safe", Some(&reason), item_ast);
+    #[cfg(feature = "strict")]
+    expanded.extend(assertion);
+    expanded
+}
+
+/// Check that a string literal holds a calendar-valid `YYYY-MM-DD` date.
+fn validate_iso_date(lit: &LitStr) -> Result<(), Error> {
+    let value = lit.value();
+    if is_iso_date(&value) {
+        Ok(())
+    } else {
+        Err(Error::new(
+            lit.span(),
+            format!(
+                "expected an ISO-8601 date like \"2025-01-01\", found \"{}\"",
+                value
+            ),
+        ))
+    }
+}
+
+/// Check that a string literal holds a version or a `YYYY-MM-DD` date.
+///
+/// Date-shaped values such as `2024-13-01` must be valid dates rather than
+/// passing as version `2024` with a pre-release suffix, matching how the
+/// scanner reads them.
+#[cfg(any(test, feature = "strict-revisit"))]
+fn validate_revisit(lit: &LitStr) -> Result<(), Error> {
+    let value = lit.value();
+    let parts: Vec<&str> = value.trim().split('-').collect();
+    let date_shaped =
+        parts.len() == 3 && parts.iter().all(|p| p.chars().all(|c| c.is_ascii_digit()));
+    let is_valid = if date_shaped {
+        is_iso_date(&value)
+    } else {
+        is_version(&value)
+    };
+
+    if is_valid {
+        Ok(())
+    } else {
+        Err(Error::new(
+            lit.span(),
+            format!(
+                "expected a version like \"v2.0\" or an ISO-8601 date like \"2025-01-01\", found \"{}\"; \
+                 put free-form notes in #[needs] instead",
+                value
+            ),
+        ))
+    }
+}
+
+/// Whether `value` is a version of one to three numeric components, optionally
+/// `v`-prefixed and followed by a `-pre` or `+build` suffix.
+#[cfg(any(test, feature = "strict-revisit"))]
+fn is_version(value: &str) -> bool {
+    let value = value.trim();
+    let value = value.strip_prefix(['v', 'V']).unwrap_or(value);
+    let (core, suffix) = match value.find(['-', '+']) {
+        Some(index) => (&value[..index], Some(&value[index + 1..])),
+        None => (value, None),
+    };
+
+    let components: Vec<&str> = core.split('.').collect();
+    components.len() <= 3
+        && components
+            .iter()
+            .all(|c| !c.is_empty() && c.chars().all(|ch| ch.is_ascii_digit()))
+        && suffix.is_none_or(|s| !s.is_empty() && !s.contains(char::is_whitespace))
+}
+
+/// Check that a string literal holds `"<YYYY-MM-DD> by <reviewer>"`.
+fn validate_review(lit: &LitStr) -> Result<(), Error> {
+    let value = lit.value();
+    let is_valid = match value.trim().split_once(" by ") {
+        Some((date, reviewer)) => is_iso_date(date) && !reviewer.trim().is_empty(),
+        None => false,
+    };
+
+    if is_valid {
+        Ok(())
+    } else {
+        Err(Error::new(
+            lit.span(),
+            format!(
+                "expected \"<YYYY-MM-DD> by <reviewer>\" like \"2024-03-01 by alice\", found \"{}\"",
+                value
+            ),
+        ))
+    }
+}
+
+/// Whether `value` is a calendar-valid `YYYY-MM-DD` date.
+fn is_iso_date(value: &str) -> bool {
+    let parts: Vec<&str> = value.trim().split('-').collect();
+    match parts.as_slice() {
+        [year, month, day] if year.len() == 4 && month.len() == 2 && day.len() == 2 => {
+            match (
+                year.parse::<u32>(),
+                month.parse::<u32>(),
+                day.parse::<u32>(),
+            ) {
+                (Ok(year), Ok(month), Ok(day)) => {
+                    (1..=12).contains(&month) && (1..=days_in_month(year, month)).contains(&day)
+                },
+                _ => false,
+            }
+        },
+        _ => false,
+    }
+}
+
+/// Number of days in the given month, accounting for leap years.
+fn days_in_month(year: u32, month: u32) -> u32 {
+    match month {
+        2 if (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every attribute macro this crate exports, read from its own source
+    fn exported_attributes() -> Vec<String> {
+        let file = syn::parse_file(include_str!("lib.rs")).expect("lib.rs parses");
+        file.items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Fn(function)
+                    if function
+                        .attrs
+                        .iter()
+                        .any(|attr| attr.path().is_ident("proc_macro_attribute")) =>
+                {
+                    Some(function.sig.ident.to_string())
+                },
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn exported_attributes_match_macro_names() {
+        let mut exported: Vec<String> = exported_attributes()
+            .into_iter()
+            .filter(|name| name != code_status_core::STATUS_ATTRIBUTE)
+            .collect();
+        exported.sort();
+
+        let mut registered: Vec<&str> = code_status_core::MACRO_NAMES.to_vec();
+        registered.sort_unstable();
+
+        assert_eq!(exported, registered);
+    }
+
+    fn revisit(value: &str) -> Result<(), Error> {
+        validate_revisit(&syn::parse_str(&format!("{:?}", value)).expect("string literal"))
+    }
+
+    #[test]
+    fn revisit_accepts_versions() {
+        for value in ["2", "v2.1", "V1.2.3", "2.0.0-beta.1", "1.4+build5"] {
+            assert!(revisit(value).is_ok(), "{:?}", value);
+        }
+    }
+
+    #[test]
+    fn revisit_accepts_dates() {
+        for value in ["2024-03-01", "2024-02-29"] {
+            assert!(revisit(value).is_ok(), "{:?}", value);
+        }
+    }
+
+    #[test]
+    fn revisit_rejects_garbage() {
+        for value in [
+            "v.2",
+            "next quarter",
+            "",
+            "v",
+            "1.2.3.4",
+            "2-",
+            "2024-13-01",
+            "2023-02-29",
+        ] {
+            let error = revisit(value).expect_err(value);
+            assert!(
+                error.to_string().contains("expected a version"),
+                "{}",
+                error
+            );
+        }
+    }
+
+    fn assumption(description: &str, item: Item) -> Item {
+        let description = syn::parse_str(&format!("{:?}", description)).expect("string literal");
+        check_assumption(&description, item)
+    }
+
+    #[cfg(feature = "check-assumptions")]
+    fn first_statement(item: &Item) -> String {
+        match item {
+            Item::Fn(item_fn) => item_fn
+                .block
+                .stmts
+                .first()
+                .map(|stmt| quote!(#stmt).to_string())
+                .unwrap_or_default(),
+            _ => String::new(),
+        }
+    }
+
+    #[cfg(feature = "check-assumptions")]
+    #[test]
+    fn checkable_assumption_becomes_a_debug_assert() {
+        let item = assumption(
+            "!items.is_empty()",
+            syn::parse_quote! { fn first(items: &[u8]) -> u8 { items[0] } },
+        );
+
+        let check = first_statement(&item);
+        assert!(check.starts_with("debug_assert !"), "{}", check);
+        assert!(check.contains("! items . is_empty ()"), "{}", check);
+        assert!(
+            check.contains("assumption violated: !items.is_empty()"),
+            "{}",
+            check
+        );
+    }
+
+    #[test]
+    fn prose_assumption_leaves_the_function_untouched() {
+        let original: Item = syn::parse_quote! { fn first(items: &[u8]) -> u8 { items[0] } };
+        let item = assumption("callers never pass an empty slice", original.clone());
+
+        assert_eq!(quote!(#item).to_string(), quote!(#original).to_string());
+    }
+
+    #[cfg(not(feature = "check-assumptions"))]
+    #[test]
+    fn assumptions_are_unchecked_without_the_feature() {
+        let original: Item = syn::parse_quote! { fn first(items: &[u8]) -> u8 { items[0] } };
+        let item = assumption("!items.is_empty()", original.clone());
+
+        assert_eq!(quote!(#item).to_string(), quote!(#original).to_string());
+    }
 }
@@ -0,0 +1,16 @@
+//! Compile tests for the arguments markers accept.
+
+#[test]
+fn needs_arguments() {
+    let cases = trybuild::TestCases::new();
+    cases.pass("tests/ui/needs_legacy.rs");
+    cases.pass("tests/ui/needs_keys.rs");
+    cases.compile_fail("tests/ui/needs_unknown_key.rs");
+}
+
+#[test]
+fn injection_classes() {
+    let cases = trybuild::TestCases::new();
+    cases.pass("tests/ui/injection_class.rs");
+    cases.compile_fail("tests/ui/injection_class_unknown.rs");
+}
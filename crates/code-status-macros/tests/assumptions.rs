@@ -0,0 +1,10 @@
+//! Compile tests for the `check-assumptions` feature.
+//!
+//! Run with `cargo test -p code-status-macros --features check-assumptions`.
+
+#![cfg(feature = "check-assumptions")]
+
+#[test]
+fn checked_assumptions() {
+    trybuild::TestCases::new().pass("tests/ui/assumptions.rs");
+}
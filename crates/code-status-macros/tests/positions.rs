@@ -0,0 +1,13 @@
+//! Compile tests for the positions a marker may be written in.
+
+#[test]
+fn nested_items() {
+    trybuild::TestCases::new().pass("tests/ui/nested_items.rs");
+}
+
+/// Attribute macros on statements need `proc_macro_hygiene` and `stmt_expr_attributes`
+#[rustversion::nightly]
+#[test]
+fn statements() {
+    trybuild::TestCases::new().pass("tests/ui/statements.rs");
+}
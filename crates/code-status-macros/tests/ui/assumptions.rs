@@ -0,0 +1,22 @@
+use code_status_macros::assumptions;
+
+#[assumptions("!samples.is_empty()")]
+fn first_sample(samples: &[u32]) -> u32 {
+    samples.first().copied().unwrap_or_default()
+}
+
+#[assumptions("callers pass samples sorted ascending")]
+fn smallest_sample(samples: &[u32]) -> u32 {
+    samples.first().copied().unwrap_or_default()
+}
+
+fn main() {
+    assert_eq!(first_sample(&[3, 1]), 3);
+
+    let violated = std::panic::catch_unwind(|| first_sample(&[]));
+    let message = violated.expect_err("checkable assumption asserts");
+    let message = message.downcast_ref::<String>().expect("formatted message");
+    assert_eq!(message, "assumption violated: !samples.is_empty()");
+
+    assert_eq!(smallest_sample(&[]), 0);
+}
@@ -0,0 +1,15 @@
+use code_status_macros::requires_input_sanitization;
+
+#[requires_input_sanitization("SQL")]
+fn find_user(name: &str) -> String {
+    format!("SELECT * FROM users WHERE name = '{}'", name)
+}
+
+#[requires_input_sanitization(" html ")]
+fn greeting(name: &str) -> String {
+    format!("<p>Hello, {}</p>", name)
+}
+
+fn main() {
+    let _ = (find_user("ada"), greeting("ada"));
+}
@@ -0,0 +1,8 @@
+use code_status_macros::requires_input_sanitization;
+
+#[requires_input_sanitization("LDAP")]
+fn find_user(name: &str) -> String {
+    format!("(uid={})", name)
+}
+
+fn main() {}
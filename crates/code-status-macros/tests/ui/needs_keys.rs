@@ -0,0 +1,14 @@
+use code_status_macros::needs;
+
+#[needs(reason = "proper error handling", issue = "PROJ-42", priority = "high")]
+fn parse(input: &str) -> Option<u16> {
+    input.parse().ok()
+}
+
+#[needs(issue = "PROJ-43")]
+struct Config;
+
+fn main() {
+    assert_eq!(parse("8080"), Some(8080));
+    let _ = Config;
+}
@@ -0,0 +1,10 @@
+use code_status_macros::needs;
+
+#[needs("proper error handling")]
+fn parse(input: &str) -> Option<u16> {
+    input.parse().ok()
+}
+
+fn main() {
+    assert_eq!(parse("8080"), Some(8080));
+}
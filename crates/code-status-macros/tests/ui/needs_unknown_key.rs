@@ -0,0 +1,8 @@
+use code_status_macros::needs;
+
+#[needs(reason = "proper error handling", owner = "platform")]
+fn parse(input: &str) -> Option<u16> {
+    input.parse().ok()
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+use code_status_macros::{needs, panic_path, untested};
+
+fn main() {
+    #[untested]
+    fn helper() -> u16 {
+        8080
+    }
+
+    #[needs("a real config type")]
+    struct Config {
+        port: Option<u16>,
+    }
+
+    #[panic_path("port is validated at startup")]
+    const DEFAULT: Config = Config { port: None };
+
+    let config = Config {
+        port: Some(helper()),
+    };
+    let _ = (config.port.unwrap(), DEFAULT.port);
+}
@@ -0,0 +1,22 @@
+#![feature(proc_macro_hygiene, stmt_expr_attributes)]
+
+use code_status_macros::{needs, panic_path, untested};
+
+fn port(config: Option<u16>) -> u16 {
+    #[panic_path("port is validated at startup")]
+    let port = config.unwrap();
+
+    #[untested]
+    let _ = port.checked_add(1);
+
+    #[needs("a configurable fallback")]
+    let fallback = 8080;
+
+    let doubled = #[untested] port.saturating_mul(2);
+
+    if doubled == 0 { fallback } else { port }
+}
+
+fn main() {
+    assert_eq!(port(Some(80)), 80);
+}
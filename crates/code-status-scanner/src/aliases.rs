@@ -0,0 +1,146 @@
+//! Detection of renamed imports such as `use code_status_macros::untested as needs_testing;`.
+
+use std::collections::HashMap;
+
+use regex::Regex;
+use syn::{visit::Visit, ItemUse, UseTree};
+
+/// Name of the macros crate as it appears in `use` paths
+pub const MACROS_CRATE: &str = "code_status_macros";
+
+/// Collects `alias -> canonical macro name` pairs from `use` declarations
+struct AliasCollector<'a> {
+    /// Names of the macros being scanned for
+    macro_names: &'a [&'a str],
+    aliases: HashMap<String, String>,
+}
+
+impl AliasCollector<'_> {
+    /// Walk a use tree, remembering whether the path so far went through the macros crate
+    fn collect(&mut self, tree: &UseTree, in_macros_crate: bool) {
+        match tree {
+            UseTree::Path(path) => {
+                let in_macros_crate = in_macros_crate || path.ident == MACROS_CRATE;
+                self.collect(&path.tree, in_macros_crate);
+            },
+            UseTree::Group(group) => {
+                for item in &group.items {
+                    self.collect(item, in_macros_crate);
+                }
+            },
+            UseTree::Rename(rename) if in_macros_crate => {
+                let original = rename.ident.to_string();
+                if self.macro_names.contains(&original.as_str()) {
+                    self.aliases.insert(rename.rename.to_string(), original);
+                }
+            },
+            _ => {},
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for AliasCollector<'_> {
+    fn visit_item_use(&mut self, item: &'ast ItemUse) {
+        self.collect(&item.tree, false);
+    }
+}
+
+/// Build detection regexes for aliases of `macro_names` imported in `content`
+///
+/// Each regex is paired with the canonical macro name it stands for, so that
+/// matches are reported under the original name. Files that fail to parse
+/// yield no aliases.
+pub fn alias_regexes(content: &str, macro_names: &[&str]) -> Vec<(String, Regex)> {
+    let file = match syn::parse_file(content) {
+        Ok(file) => file,
+        Err(_) => return Vec::new(),
+    };
+
+    aliases(&file, macro_names)
+        .into_iter()
+        .filter_map(|(alias, canonical)| {
+            let pattern = crate::attribute_pattern(&alias);
+            Regex::new(&pattern).ok().map(|regex| (canonical, regex))
+        })
+        .collect()
+}
+
+/// Collect `alias -> canonical macro name` pairs for `macro_names` imported in a parsed file
+pub fn aliases(file: &syn::File, macro_names: &[&str]) -> HashMap<String, String> {
+    let mut collector = AliasCollector {
+        macro_names,
+        aliases: HashMap::new(),
+    };
+    collector.visit_file(file);
+    collector.aliases
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::{Engine, Scanner};
+
+    /// `(line, macro)` of each finding with either engine, following aliases
+    fn scan(source: &str, engine: Engine) -> Vec<(usize, String)> {
+        let file = Path::new("src/lib.rs");
+        Scanner::new()
+            .engine(engine)
+            .follow_use_aliases(true)
+            .scan_source(file, file, source)
+            .into_iter()
+            .map(|instance| (instance.line, instance.macro_name))
+            .collect()
+    }
+
+    #[test]
+    fn renamed_import_is_reported_under_its_original_name() {
+        let source = "\
+use code_status_macros::untested as needs_testing;
+
+#[needs_testing]
+fn parse() {}
+";
+        for engine in [Engine::Regex, Engine::Ast] {
+            assert_eq!(scan(source, engine), [(3, "untested".to_string())]);
+        }
+    }
+
+    #[test]
+    fn grouped_renamed_import_is_reported_under_its_original_name() {
+        let source = "\
+use code_status_macros::{needs, untested as x};
+
+#[x]
+fn parse() {}
+";
+        for engine in [Engine::Regex, Engine::Ast] {
+            assert_eq!(scan(source, engine), [(3, "untested".to_string())]);
+        }
+    }
+
+    #[test]
+    fn only_renames_from_the_macros_crate_are_aliases() {
+        let file = syn::parse_file(
+            "\
+use code_status_macros::{untested as x, perf_critical as hot};
+use other_crate::untested as y;
+use code_status_macros::not_a_marker as z;
+",
+        )
+        .unwrap();
+
+        let mut found: Vec<_> = aliases(&file, &["untested", "perf_critical"])
+            .into_iter()
+            .collect();
+        found.sort();
+        assert_eq!(
+            found,
+            [
+                ("hot".to_string(), "perf_critical".to_string()),
+                ("x".to_string(), "untested".to_string())
+            ]
+        );
+    }
+}
@@ -0,0 +1,274 @@
+//! Scanning by parsing files with `syn`, for `--engine ast`.
+//!
+//! Unlike the line-based regex scan, this knows which item each marker is
+//! attached to and never reports text in comments or string literals.
+
+use std::{collections::HashMap, path::Path};
+
+use proc_macro2::Span;
+use syn::{
+    spanned::Spanned, visit::Visit, Attribute, Expr, ImplItem, Item, Local, Meta, Stmt, TraitItem,
+};
+
+use crate::{
+    aliases, decode_argument, find_item_name, finding_id, item_context, join_continuation, mask,
+    status, strip_trailing_comment, MacroInstance, ScanMode, STATUS_ATTRIBUTE,
+};
+
+/// Scan a file's items for the markers in `macro_names`, or return `None` when it doesn't parse
+pub fn scan_file(
+    path: &Path,
+    id_path: &Path,
+    content: &str,
+    macro_names: &[&str],
+    mode: ScanMode,
+) -> Option<Vec<MacroInstance>> {
+    let file = syn::parse_file(content).ok()?;
+    let aliases = if mode.follow_use_aliases {
+        aliases::aliases(&file, macro_names)
+    } else {
+        HashMap::new()
+    };
+
+    let mut collector = Collector {
+        path,
+        id_path,
+        macro_names,
+        lines: content.lines().collect(),
+        context_lines: mode.context_lines,
+        aliases,
+        instances: Vec::new(),
+    };
+    collector.visit_file(&file);
+    Some(collector.instances)
+}
+
+/// Records the markers on each item it visits
+struct Collector<'a> {
+    path: &'a Path,
+    id_path: &'a Path,
+    /// Names of the macros being scanned for
+    macro_names: &'a [&'a str],
+    lines: Vec<&'a str>,
+    /// How many lines of each item, from its head on, become the context
+    context_lines: usize,
+    /// `alias -> canonical macro name` pairs imported by the file
+    aliases: HashMap<String, String>,
+    instances: Vec<MacroInstance>,
+}
+
+impl Collector<'_> {
+    /// The canonical name of the marker `attr` invokes, if it is one
+    fn marker_name(&self, attr: &Attribute) -> Option<String> {
+        let segments: Vec<String> = attr
+            .path()
+            .segments
+            .iter()
+            .map(|segment| segment.ident.to_string())
+            .collect();
+        let name = match segments.as_slice() {
+            [name] => name,
+            [krate, name] if krate == aliases::MACROS_CRATE => name,
+            _ => return None,
+        };
+
+        if self.macro_names.contains(&name.as_str()) || name == STATUS_ATTRIBUTE {
+            Some(name.clone())
+        } else {
+            self.aliases.get(name).cloned()
+        }
+    }
+
+    /// Record the markers among `attrs` on an item of `kind`
+    ///
+    /// `head` is the span of the item's name (or `impl` keyword), whose line
+    /// starts the finding's context.
+    fn record(
+        &mut self,
+        attrs: &[Attribute],
+        kind: &'static str,
+        name: Option<String>,
+        head: Span,
+    ) {
+        let context = self
+            .lines
+            .get(head.start().line.saturating_sub(1)..)
+            .map_or_else(String::new, |lines| item_context(lines, self.context_lines));
+        let item_name =
+            name.or_else(|| find_item_name(&[context.lines().next().unwrap_or_default()]));
+
+        for attr in attrs {
+            let Some(macro_name) = self.marker_name(attr) else {
+                continue;
+            };
+            let markers = match &attr.meta {
+                // `#[status(...)]` stands for each marker it lists
+                Meta::List(list) if macro_name == STATUS_ATTRIBUTE => {
+                    status::entries(list.tokens.clone(), self.macro_names)
+                },
+                Meta::List(list) => {
+                    let argument = list
+                        .delimiter
+                        .span()
+                        .join()
+                        .source_text()
+                        .map_or_else(String::new, |text| decode_argument(&one_line(&text)));
+                    vec![(macro_name, argument)]
+                },
+                _ if macro_name == STATUS_ATTRIBUTE => Vec::new(),
+                _ => vec![(macro_name, String::new())],
+            };
+
+            for (macro_name, argument) in markers {
+                let mut instance = MacroInstance {
+                    id: String::new(),
+                    path: self.path.to_path_buf(),
+                    line: attr.pound_token.span.start().line,
+                    macro_name,
+                    argument: Some(argument),
+                    context: context.clone(),
+                    item_name: item_name.clone(),
+                    item_kind: Some(kind),
+                    blame: None,
+                };
+                instance.id = finding_id(
+                    self.id_path,
+                    &instance.macro_name,
+                    instance
+                        .item_name
+                        .as_deref()
+                        .unwrap_or(instance.signature()),
+                    instance.argument_value().unwrap_or_default(),
+                );
+                self.instances.push(instance);
+            }
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for Collector<'_> {
+    fn visit_item(&mut self, item: &'ast Item) {
+        let annotated = match item {
+            Item::Fn(f) => Some((&f.attrs, "fn", &f.sig.ident)),
+            Item::Struct(s) => Some((&s.attrs, "struct", &s.ident)),
+            Item::Enum(e) => Some((&e.attrs, "enum", &e.ident)),
+            Item::Union(u) => Some((&u.attrs, "union", &u.ident)),
+            Item::Trait(t) => Some((&t.attrs, "trait", &t.ident)),
+            Item::Type(t) => Some((&t.attrs, "type", &t.ident)),
+            Item::Const(c) => Some((&c.attrs, "const", &c.ident)),
+            Item::Static(s) => Some((&s.attrs, "static", &s.ident)),
+            Item::Mod(m) => Some((&m.attrs, "mod", &m.ident)),
+            Item::Macro(m) => {
+                let name = m.ident.as_ref().map(ToString::to_string);
+                self.record(&m.attrs, "macro", name, m.mac.bang_token.span);
+                None
+            },
+            Item::Impl(i) => {
+                self.record(&i.attrs, "impl", None, i.impl_token.span);
+                None
+            },
+            _ => None,
+        };
+        if let Some((attrs, kind, ident)) = annotated {
+            self.record(attrs, kind, Some(ident.to_string()), ident.span());
+        }
+
+        syn::visit::visit_item(self, item);
+    }
+
+    fn visit_impl_item(&mut self, item: &'ast ImplItem) {
+        match item {
+            ImplItem::Fn(f) => {
+                let ident = &f.sig.ident;
+                self.record(&f.attrs, "method", Some(ident.to_string()), ident.span());
+            },
+            ImplItem::Const(c) => {
+                self.record(&c.attrs, "const", Some(c.ident.to_string()), c.ident.span())
+            },
+            ImplItem::Type(t) => {
+                self.record(&t.attrs, "type", Some(t.ident.to_string()), t.ident.span())
+            },
+            _ => {},
+        }
+
+        syn::visit::visit_impl_item(self, item);
+    }
+
+    fn visit_trait_item(&mut self, item: &'ast TraitItem) {
+        match item {
+            TraitItem::Fn(f) => {
+                let ident = &f.sig.ident;
+                self.record(&f.attrs, "method", Some(ident.to_string()), ident.span());
+            },
+            TraitItem::Const(c) => {
+                self.record(&c.attrs, "const", Some(c.ident.to_string()), c.ident.span())
+            },
+            TraitItem::Type(t) => {
+                self.record(&t.attrs, "type", Some(t.ident.to_string()), t.ident.span())
+            },
+            _ => {},
+        }
+
+        syn::visit::visit_trait_item(self, item);
+    }
+
+    fn visit_stmt(&mut self, stmt: &'ast Stmt) {
+        match stmt {
+            Stmt::Local(Local {
+                attrs, let_token, ..
+            }) => {
+                self.record(attrs, "let", None, let_token.span);
+            },
+            Stmt::Macro(m) => self.record(&m.attrs, "expr", None, m.mac.span()),
+            _ => {},
+        }
+
+        syn::visit::visit_stmt(self, stmt);
+    }
+
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        if let Some(attrs) = expr_attrs(expr) {
+            self.record(attrs, "expr", None, expr.span());
+        }
+
+        syn::visit::visit_expr(self, expr);
+    }
+}
+
+/// The attributes of the expressions a marker is likely to be put on
+///
+/// Literals, operators and the like are left out, so markers on them are only
+/// found by the regex engine.
+fn expr_attrs(expr: &Expr) -> Option<&Vec<Attribute>> {
+    match expr {
+        Expr::Assign(e) => Some(&e.attrs),
+        Expr::Async(e) => Some(&e.attrs),
+        Expr::Await(e) => Some(&e.attrs),
+        Expr::Block(e) => Some(&e.attrs),
+        Expr::Call(e) => Some(&e.attrs),
+        Expr::Closure(e) => Some(&e.attrs),
+        Expr::ForLoop(e) => Some(&e.attrs),
+        Expr::If(e) => Some(&e.attrs),
+        Expr::Loop(e) => Some(&e.attrs),
+        Expr::Macro(e) => Some(&e.attrs),
+        Expr::Match(e) => Some(&e.attrs),
+        Expr::MethodCall(e) => Some(&e.attrs),
+        Expr::Try(e) => Some(&e.attrs),
+        Expr::Unsafe(e) => Some(&e.attrs),
+        Expr::While(e) => Some(&e.attrs),
+        _ => None,
+    }
+}
+
+/// Join a possibly multi-line argument onto one line, dropping comments
+fn one_line(text: &str) -> String {
+    let code = mask::code_only(text);
+    let mut lines = text.lines().zip(code.lines());
+    let mut joined = lines.next().map_or_else(String::new, |(line, code)| {
+        strip_trailing_comment(line, code).trim().to_string()
+    });
+    for (line, code) in lines {
+        join_continuation(&mut joined, strip_trailing_comment(line, code).trim());
+    }
+    joined
+}
@@ -0,0 +1,96 @@
+//! Baseline files of already-known findings, written by `baseline` and applied with `--baseline`.
+//!
+//! Entries are keyed by finding ID, which fingerprints the file, macro,
+//! annotated item and argument but not the line, so edits elsewhere in a file
+//! don't churn the baseline.
+
+use std::{collections::HashMap, fs, path::Path};
+
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+
+use crate::MacroInstance;
+
+/// The on-disk baseline
+#[derive(Serialize, Deserialize)]
+struct Baseline {
+    findings: Vec<Entry>,
+}
+
+/// One known finding; only `id` is used for matching, the rest helps humans prune the file
+#[derive(Serialize, Deserialize)]
+pub struct Entry {
+    id: String,
+    macro_name: String,
+    path: String,
+    item: Option<String>,
+}
+
+/// Write every finding to `file`
+pub fn write(instances: &[MacroInstance], file: &Path) -> Result<(), String> {
+    let baseline = Baseline {
+        findings: instances
+            .iter()
+            .map(|instance| Entry {
+                id: instance.id.clone(),
+                macro_name: instance.macro_name.clone(),
+                path: instance.path.display().to_string(),
+                item: instance.item_name.clone(),
+            })
+            .collect(),
+    };
+    let json = serde_json::to_string_pretty(&baseline).map_err(|err| err.to_string())?;
+    fs::write(file, json + "\n").map_err(|err| format!("{}: {}", file.display(), err))
+}
+
+/// Read the entries of a baseline file
+pub fn load(file: &Path) -> Result<Vec<Entry>, String> {
+    let text = fs::read_to_string(file).map_err(|err| format!("{}: {}", file.display(), err))?;
+    serde_json::from_str::<Baseline>(&text)
+        .map(|baseline| baseline.findings)
+        .map_err(|err| format!("{}: {}", file.display(), err))
+}
+
+/// Drop findings recorded in the baseline, returning entries that matched nothing
+///
+/// Each entry suppresses one finding, so a second identical marker added to an
+/// item is still reported.
+pub fn subtract(instances: &mut Vec<MacroInstance>, entries: Vec<Entry>) -> Vec<Entry> {
+    let mut known: HashMap<String, Vec<Entry>> = HashMap::new();
+    for entry in entries {
+        known.entry(entry.id.clone()).or_default().push(entry);
+    }
+
+    instances.retain(|instance| known.get_mut(&instance.id).and_then(Vec::pop).is_none());
+
+    let mut stale: Vec<Entry> = known.into_values().flatten().collect();
+    stale.sort_by(|a, b| (&a.path, &a.macro_name).cmp(&(&b.path, &b.macro_name)));
+    stale
+}
+
+/// Tell the user, on stderr, which baseline entries no longer match any finding
+pub fn print_stale(stale: &[Entry]) {
+    if stale.is_empty() {
+        return;
+    }
+
+    eprintln!(
+        "{}",
+        format!(
+            "{} baseline entries no longer match a finding; rerun `baseline` to prune them:",
+            stale.len()
+        )
+        .yellow()
+    );
+    for entry in stale {
+        eprintln!(
+            "  {} #[{}]{}",
+            entry.path,
+            entry.macro_name,
+            entry
+                .item
+                .as_deref()
+                .map_or_else(String::new, |item| format!(" on {}", item))
+        );
+    }
+}
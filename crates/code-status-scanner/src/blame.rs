@@ -0,0 +1,105 @@
+//! Attribution of findings to the commit that last touched them, for `--blame`.
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use rayon::prelude::*;
+use serde::Serialize;
+
+use crate::{date::Date, MacroInstance};
+
+/// Who last changed a line, and when
+#[derive(Debug, Clone, Serialize)]
+pub struct BlameInfo {
+    /// Author name of the commit
+    pub author: String,
+    /// Date the commit was made
+    pub commit_date: Date,
+}
+
+/// Attach the author and commit date of each finding's line
+///
+/// Files outside a git repository, untracked files and lines not yet
+/// committed are left without blame information.
+pub fn annotate(instances: &mut [MacroInstance]) {
+    let mut paths: Vec<&PathBuf> = instances.iter().map(|i| &i.path).collect();
+    paths.sort();
+    paths.dedup();
+
+    let blamed: HashMap<PathBuf, HashMap<usize, BlameInfo>> = paths
+        .into_par_iter()
+        .filter_map(|path| Some((path.clone(), blame_file(path)?)))
+        .collect();
+
+    for instance in instances {
+        instance.blame = blamed
+            .get(&instance.path)
+            .and_then(|lines| lines.get(&instance.line))
+            .cloned();
+    }
+}
+
+/// Run `git blame` on a file, mapping each committed line to its blame
+fn blame_file(path: &Path) -> Option<HashMap<usize, BlameInfo>> {
+    let dir = path.parent().filter(|d| !d.as_os_str().is_empty());
+    let output = Command::new("git")
+        .current_dir(dir.unwrap_or_else(|| Path::new(".")))
+        .args(["blame", "--porcelain", "--"])
+        .arg(path.file_name()?)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    Some(parse_porcelain(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Parse `git blame --porcelain` output
+///
+/// Commit details are only printed the first time a commit appears, so they
+/// are remembered by hash. The all-zero hash marks uncommitted lines.
+fn parse_porcelain(output: &str) -> HashMap<usize, BlameInfo> {
+    let mut commits: HashMap<&str, (Option<&str>, Option<i64>)> = HashMap::new();
+    let mut lines = HashMap::new();
+    let mut current: Option<(&str, usize)> = None;
+
+    for line in output.lines() {
+        if line.starts_with('\t') {
+            if let Some((hash, final_line)) = current.take() {
+                let committed = hash.bytes().any(|b| b != b'0');
+                if let Some((Some(author), Some(time))) = commits.get(hash).filter(|_| committed) {
+                    lines.insert(
+                        final_line,
+                        BlameInfo {
+                            author: (*author).to_string(),
+                            commit_date: Date::from_unix_seconds(*time),
+                        },
+                    );
+                }
+            }
+        } else if let Some(author) = line.strip_prefix("author ") {
+            if let Some((hash, _)) = current {
+                commits.entry(hash).or_default().0 = Some(author);
+            }
+        } else if let Some(time) = line.strip_prefix("author-time ") {
+            if let Some((hash, _)) = current {
+                commits.entry(hash).or_default().1 = time.trim().parse().ok();
+            }
+        } else if current.is_none() {
+            // A header: `<hash> <original line> <final line> [<group size>]`
+            let mut fields = line.split(' ');
+            let hash = fields.next();
+            let final_line = fields.nth(1).and_then(|n| n.parse().ok());
+            if let (Some(hash), Some(final_line)) = (hash, final_line) {
+                commits.entry(hash).or_default();
+                current = Some((hash, final_line));
+            }
+        }
+    }
+
+    lines
+}
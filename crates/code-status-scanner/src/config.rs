@@ -0,0 +1,87 @@
+//! The project config file, `code-status.toml` at the scan root or given with `--config`.
+//!
+//! ```toml
+//! extra_macros = ["flaky", "hot_path"]
+//!
+//! [severity]
+//! benchmark_candidate = "warning"
+//! panic_path = "warning"
+//! ```
+
+use std::{collections::HashMap, fs, path::Path};
+
+use serde::Deserialize;
+
+use crate::{parse_macro_name, severity::Severities, Severity, MACRO_NAMES};
+
+/// Name of the config file picked up from the scan root
+pub const FILE_NAME: &str = "code-status.toml";
+
+/// Project settings, with defaults for anything the file leaves out
+#[derive(Debug, Default)]
+pub struct Config {
+    /// Attribute names to scan for besides the built-in macros
+    pub extra_macros: Vec<String>,
+    pub severities: Severities,
+}
+
+/// The file as written
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct File {
+    #[serde(default)]
+    extra_macros: Vec<String>,
+    /// `macro = "critical" | "warning" | "info"` overrides
+    #[serde(default)]
+    severity: HashMap<String, String>,
+}
+
+/// Read `file`, or `code-status.toml` under `root` when it exists
+///
+/// `cli_extra_macros` may be re-ranked in `[severity]` like the file's own.
+pub fn load(
+    file: Option<&Path>,
+    root: &Path,
+    cli_extra_macros: &[String],
+) -> Result<Config, String> {
+    let default_file = root.join(FILE_NAME);
+    let file = match file {
+        Some(file) => file,
+        None if default_file.is_file() => &default_file,
+        None => return Ok(Config::default()),
+    };
+
+    let text = fs::read_to_string(file).map_err(|err| format!("{}: {}", file.display(), err))?;
+    let parsed: File =
+        toml::from_str(&text).map_err(|err| format!("{}: {}", file.display(), err))?;
+
+    let extra_macros = parsed
+        .extra_macros
+        .iter()
+        .map(|name| parse_macro_name(name))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| format!("{}: {} in extra_macros", file.display(), err))?;
+
+    let mut overrides = HashMap::new();
+    for (macro_name, level) in parsed.severity {
+        let known = MACRO_NAMES.contains(&macro_name.as_str())
+            || extra_macros.contains(&macro_name)
+            || cli_extra_macros.contains(&macro_name);
+        if !known {
+            return Err(format!(
+                "{}: unknown macro `{}` in [severity]",
+                file.display(),
+                macro_name
+            ));
+        }
+        let severity: Severity = level
+            .parse()
+            .map_err(|err| format!("{}: {} for `{}`", file.display(), err, macro_name))?;
+        overrides.insert(macro_name, severity);
+    }
+
+    Ok(Config {
+        extra_macros,
+        severities: Severities::new(overrides),
+    })
+}
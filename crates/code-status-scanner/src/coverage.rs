@@ -0,0 +1,365 @@
+//! Cross-checking `coverage_gap` and `untested` functions against an LCOV report,
+//! for the `coverage` subcommand.
+//!
+//! A marked function spans its signature and body as parsed from the source. It
+//! is a confirmed gap when the report has none of those lines executed, and a
+//! stale marker when any of them ran.
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use colored::Colorize;
+use syn::{visit::Visit, Attribute, ImplItemFn, ItemFn, TraitItemFn};
+
+use crate::MacroInstance;
+
+/// Markers claiming that tests don't exercise a function
+const GAP_MARKERS: &[&str] = &["coverage_gap", "untested"];
+
+/// Execution counts per line of each source file in an LCOV report
+pub struct Lcov {
+    files: Vec<SourceFile>,
+}
+
+/// One `SF` entry of an LCOV report
+struct SourceFile {
+    /// The path as the report gives it
+    path: PathBuf,
+    /// The path resolved on this machine, when the file exists here
+    canonical: Option<PathBuf>,
+    /// Execution count of each line
+    lines: HashMap<usize, u64>,
+}
+
+impl Lcov {
+    /// Read the `SF` and `DA` records of an LCOV tracefile, as written by `cargo llvm-cov --lcov`
+    pub fn load(file: &Path) -> Result<Self, String> {
+        let text =
+            fs::read_to_string(file).map_err(|err| format!("{}: {}", file.display(), err))?;
+        Ok(Self::parse(&text, file.parent().unwrap_or(Path::new(""))))
+    }
+
+    /// Parse a tracefile, resolving relative `SF` paths against `base`
+    fn parse(text: &str, base: &Path) -> Self {
+        let mut files: HashMap<PathBuf, HashMap<usize, u64>> = HashMap::new();
+        let mut current = None;
+        for record in text.lines().map(str::trim) {
+            if let Some(path) = record.strip_prefix("SF:") {
+                current = Some(PathBuf::from(path));
+            } else if let Some(data) = record.strip_prefix("DA:") {
+                let mut fields = data.split(',').map(str::trim);
+                let line = fields.next().and_then(|line| line.parse::<usize>().ok());
+                let hits = fields.next().and_then(|hits| hits.parse::<u64>().ok());
+                if let (Some(path), Some(line), Some(hits)) = (&current, line, hits) {
+                    *files
+                        .entry(path.clone())
+                        .or_default()
+                        .entry(line)
+                        .or_default() += hits;
+                }
+            } else if record == "end_of_record" {
+                current = None;
+            }
+        }
+
+        let files = files
+            .into_iter()
+            .map(|(path, lines)| SourceFile {
+                canonical: fs::canonicalize(base.join(&path)).ok(),
+                path,
+                lines,
+            })
+            .collect();
+        Self { files }
+    }
+
+    /// The line counts of a scanned file, given the scan root and the finding's path
+    ///
+    /// The file is looked up by its canonical path. A report from another
+    /// machine or checkout falls back to matching when either path ends with
+    /// the other, but only when a single file in the report matches, since
+    /// `src/lib.rs` may stand for any crate's.
+    fn lines_of(&self, root: &Path, path: &Path) -> Option<&HashMap<usize, u64>> {
+        if let Ok(canonical) = fs::canonicalize(root.join(path)) {
+            if let Some(file) = self
+                .files
+                .iter()
+                .find(|file| file.canonical.as_ref() == Some(&canonical))
+            {
+                return Some(&file.lines);
+            }
+        }
+
+        let path = path.strip_prefix(".").unwrap_or(path);
+        let mut candidates = self
+            .files
+            .iter()
+            .filter(|file| file.path.ends_with(path) || path.ends_with(&file.path));
+        match (candidates.next(), candidates.next()) {
+            (Some(file), None) => Some(&file.lines),
+            _ => None,
+        }
+    }
+}
+
+/// What the report says about a marked function
+enum Verdict {
+    /// None of the function's lines ran
+    Gap,
+    /// Some lines ran, so the marker is out of date
+    Covered { covered: usize, total: usize },
+    /// The report has no lines of the function, or it isn't a function
+    Unknown,
+}
+
+/// Print marked functions as confirmed gaps, stale markers and ones the report doesn't cover
+///
+/// Finding paths are relative to `root`.
+pub fn report(instances: &[MacroInstance], root: &Path, lcov: &Lcov, lcov_path: &Path) {
+    let marked: Vec<&MacroInstance> = instances
+        .iter()
+        .filter(|i| GAP_MARKERS.contains(&i.macro_name.as_str()))
+        .collect();
+    if marked.is_empty() {
+        println!(
+            "{}",
+            "No #[coverage_gap] or #[untested] markers found.".yellow()
+        );
+        return;
+    }
+
+    let mut spans: HashMap<&Path, Vec<FnSpan>> = HashMap::new();
+    let mut gaps = Vec::new();
+    let mut stale = Vec::new();
+    let mut unknown = Vec::new();
+    for instance in marked.iter().copied() {
+        let spans = spans
+            .entry(&instance.path)
+            .or_insert_with(|| fn_spans(&root.join(&instance.path)));
+        match verdict(instance, spans, lcov.lines_of(root, &instance.path)) {
+            Verdict::Gap => gaps.push(instance),
+            Verdict::Covered { covered, total } => stale.push((instance, covered, total)),
+            Verdict::Unknown => unknown.push(instance),
+        }
+    }
+
+    println!(
+        "{}",
+        format!(
+            "Checked {} #[coverage_gap] and #[untested] items against {}:",
+            marked.len(),
+            lcov_path.display()
+        )
+        .green()
+    );
+    println!();
+
+    if !gaps.is_empty() {
+        println!(
+            "{}",
+            format!("Confirmed gaps ({}):", gaps.len()).red().bold()
+        );
+        for instance in gaps {
+            print_marker(instance, None);
+        }
+    }
+    if !stale.is_empty() {
+        println!(
+            "{}",
+            format!("Stale markers to remove ({}):", stale.len())
+                .yellow()
+                .bold()
+        );
+        for (instance, covered, total) in stale {
+            print_marker(
+                instance,
+                Some(format!("{} of {} lines covered", covered, total)),
+            );
+        }
+    }
+    if !unknown.is_empty() {
+        println!(
+            "{}",
+            format!("Not in the coverage report ({}):", unknown.len())
+                .cyan()
+                .bold()
+        );
+        for instance in unknown {
+            print_marker(instance, None);
+        }
+    }
+}
+
+/// Judge one marker by the report's counts for the lines of its file
+fn verdict(
+    instance: &MacroInstance,
+    spans: &[FnSpan],
+    lines: Option<&HashMap<usize, u64>>,
+) -> Verdict {
+    let Some(span) = spans
+        .iter()
+        .find(|span| span.marker_lines.contains(&instance.line))
+    else {
+        return Verdict::Unknown;
+    };
+    let Some(lines) = lines else {
+        return Verdict::Unknown;
+    };
+
+    let counts: Vec<u64> = (span.start..=span.end)
+        .filter_map(|line| lines.get(&line).copied())
+        .collect();
+    let covered = counts.iter().filter(|&&hits| hits > 0).count();
+    match (counts.len(), covered) {
+        (0, _) => Verdict::Unknown,
+        (_, 0) => Verdict::Gap,
+        (total, covered) => Verdict::Covered { covered, total },
+    }
+}
+
+/// Print a marker's location, macro and item, with an optional note, then its context
+fn print_marker(instance: &MacroInstance, note: Option<String>) {
+    let item = instance
+        .item_name
+        .as_deref()
+        .map_or_else(String::new, |item| format!(" on {}", item));
+    println!(
+        "  {} {}{}{}",
+        format!("{}:{}", instance.path.display(), instance.line).blue(),
+        format!("#[{}]", instance.macro_name).green(),
+        item.dimmed(),
+        note.map_or_else(String::new, |note| format!(" {}", note))
+            .yellow()
+    );
+    println!("      {}", instance.context_block("      "));
+}
+
+/// The lines of a function and of the attributes on it
+struct FnSpan {
+    /// First line of each attribute
+    marker_lines: Vec<usize>,
+    /// Lines of the signature through the closing brace
+    start: usize,
+    end: usize,
+}
+
+/// Collects a [`FnSpan`] for every function with a body
+#[derive(Default)]
+struct SpanCollector {
+    spans: Vec<FnSpan>,
+}
+
+impl SpanCollector {
+    fn record(&mut self, attrs: &[Attribute], sig: &syn::Signature, block: &syn::Block) {
+        self.spans.push(FnSpan {
+            marker_lines: attrs
+                .iter()
+                .map(|attr| attr.pound_token.span.start().line)
+                .collect(),
+            start: sig.fn_token.span.start().line,
+            end: block.brace_token.span.close().end().line,
+        });
+    }
+}
+
+impl<'ast> Visit<'ast> for SpanCollector {
+    fn visit_item_fn(&mut self, node: &'ast ItemFn) {
+        self.record(&node.attrs, &node.sig, &node.block);
+        syn::visit::visit_item_fn(self, node);
+    }
+
+    fn visit_impl_item_fn(&mut self, node: &'ast ImplItemFn) {
+        self.record(&node.attrs, &node.sig, &node.block);
+        syn::visit::visit_impl_item_fn(self, node);
+    }
+
+    fn visit_trait_item_fn(&mut self, node: &'ast TraitItemFn) {
+        if let Some(block) = &node.default {
+            self.record(&node.attrs, &node.sig, block);
+        }
+        syn::visit::visit_trait_item_fn(self, node);
+    }
+}
+
+/// Every function with a body in `file`, or none when it can't be read or parsed
+fn fn_spans(file: &Path) -> Vec<FnSpan> {
+    let Some(ast) = fs::read_to_string(file)
+        .ok()
+        .and_then(|content| syn::parse_file(&content).ok())
+    else {
+        return Vec::new();
+    };
+    let mut collector = SpanCollector::default();
+    collector.visit_file(&ast);
+    collector.spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn workspace_file(path: &str) -> String {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../..")
+            .join(path)
+            .display()
+            .to_string()
+    }
+
+    fn record(path: &str, hits: u64) -> String {
+        format!("SF:{}\nDA:1,{}\nend_of_record\n", path, hits)
+    }
+
+    #[test]
+    fn finding_resolves_to_its_own_file_among_same_named_ones() {
+        let report = [
+            record(&workspace_file("crates/code-status-core/src/lib.rs"), 1),
+            record(&workspace_file("crates/code-status-scanner/src/lib.rs"), 2),
+            record(&workspace_file("crates/code-status-macros/src/lib.rs"), 3),
+        ]
+        .concat();
+        let lcov = Lcov::parse(&report, Path::new(""));
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+
+        let lines = lcov.lines_of(root, Path::new("src/lib.rs")).unwrap();
+        assert_eq!(lines.get(&1), Some(&2));
+    }
+
+    #[test]
+    fn relative_report_paths_resolve_against_the_report() {
+        let lcov = Lcov::parse(
+            &record("crates/code-status-scanner/src/lib.rs", 4),
+            &Path::new(env!("CARGO_MANIFEST_DIR")).join("../.."),
+        );
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+
+        let lines = lcov.lines_of(root, Path::new("src/lib.rs")).unwrap();
+        assert_eq!(lines.get(&1), Some(&4));
+    }
+
+    #[test]
+    fn foreign_report_matches_by_suffix_only_when_unambiguous() {
+        let root = Path::new("/nonexistent/checkout");
+
+        let single = Lcov::parse(&record("/ci/work/repo/src/lib.rs", 5), Path::new(""));
+        let lines = single.lines_of(root, Path::new("src/lib.rs")).unwrap();
+        assert_eq!(lines.get(&1), Some(&5));
+
+        let workspace = [
+            record("/ci/work/repo/crates/a/src/lib.rs", 1),
+            record("/ci/work/repo/crates/b/src/lib.rs", 2),
+        ]
+        .concat();
+        let ambiguous = Lcov::parse(&workspace, Path::new(""));
+        assert!(ambiguous.lines_of(root, Path::new("src/lib.rs")).is_none());
+        assert_eq!(
+            ambiguous
+                .lines_of(root, Path::new("crates/b/src/lib.rs"))
+                .and_then(|lines| lines.get(&1)),
+            Some(&2)
+        );
+    }
+}
@@ -0,0 +1,148 @@
+//! Minimal calendar date handling for date-bearing macro arguments.
+
+use std::{
+    fmt,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Serialize, Serializer};
+
+/// A calendar date in the proleptic Gregorian calendar
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Date {
+    /// Four-digit year
+    pub year: i32,
+    /// Month, from 1
+    pub month: u32,
+    /// Day of the month, from 1
+    pub day: u32,
+}
+
+impl Date {
+    /// Parse an ISO-8601 `YYYY-MM-DD` date, rejecting impossible days
+    pub fn parse(input: &str) -> Option<Self> {
+        let mut parts = input.trim().splitn(3, '-');
+        let year = parts.next()?;
+        let month = parts.next()?;
+        let day = parts.next()?;
+
+        if year.len() != 4 || month.len() != 2 || day.len() != 2 {
+            return None;
+        }
+
+        let date = Self {
+            year: year.parse().ok()?,
+            month: month.parse().ok()?,
+            day: day.parse().ok()?,
+        };
+
+        if !(1..=12).contains(&date.month)
+            || !(1..=days_in_month(date.year, date.month)).contains(&date.day)
+        {
+            return None;
+        }
+
+        Some(date)
+    }
+
+    /// Today's date in UTC, derived from the system clock
+    pub fn today() -> Self {
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        Self::from_unix_seconds(secs as i64)
+    }
+
+    /// The UTC date of a Unix timestamp
+    pub fn from_unix_seconds(secs: i64) -> Self {
+        Self::from_days_since_epoch(secs.div_euclid(86_400))
+    }
+
+    /// Convert a day count relative to 1970-01-01 into a calendar date
+    fn from_days_since_epoch(days: i64) -> Self {
+        // Howard Hinnant's `civil_from_days` algorithm
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = (yoe + era * 400 + i64::from(month <= 2)) as i32;
+
+        Self { year, month, day }
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// Serialized as `YYYY-MM-DD`
+impl Serialize for Date {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Number of days in the given month, accounting for leap years
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_iso_dates() {
+        assert_eq!(
+            Date::parse(" 2025-01-31 "),
+            Some(Date {
+                year: 2025,
+                month: 1,
+                day: 31
+            })
+        );
+        assert_eq!(Date::parse("2024-02-29").map(|d| d.day), Some(29));
+    }
+
+    #[test]
+    fn rejects_malformed_and_impossible_dates() {
+        for input in [
+            "",
+            "2025-1-01",
+            "25-01-01",
+            "2025/01/01",
+            "2025-13-01",
+            "2025-00-10",
+            "2025-04-31",
+            "2023-02-29",
+            "next quarter",
+        ] {
+            assert_eq!(Date::parse(input), None, "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn converts_unix_seconds_to_utc_dates() {
+        assert_eq!(Date::from_unix_seconds(0).to_string(), "1970-01-01");
+        assert_eq!(
+            Date::from_unix_seconds(1_735_689_599).to_string(),
+            "2024-12-31"
+        );
+        assert_eq!(
+            Date::from_unix_seconds(1_735_689_600).to_string(),
+            "2025-01-01"
+        );
+    }
+}
@@ -0,0 +1,120 @@
+//! Markers per thousand lines of code, per file and rolled up per directory,
+//! for `summary --density`.
+//!
+//! Raw counts favor the largest files; density points at where debt is
+//! concentrated. Every scanned file counts towards its directories' lines,
+//! including files without markers.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use colored::Colorize;
+
+use crate::MacroInstance;
+
+/// How many of the densest files and directories are listed
+const TOP: usize = 10;
+
+/// Markers and lines of a file or directory
+#[derive(Default, Clone, Copy)]
+struct Tally {
+    markers: usize,
+    lines: usize,
+}
+
+impl Tally {
+    /// Markers per 1000 lines
+    fn density(self) -> f64 {
+        if self.lines == 0 {
+            0.0
+        } else {
+            self.markers as f64 * 1000.0 / self.lines as f64
+        }
+    }
+}
+
+/// Lines in `file`, or 0 when it can't be read
+pub fn count_lines(file: &Path) -> usize {
+    fs::read_to_string(file).map_or(0, |content| content.lines().count())
+}
+
+/// Print the densest files and directories, highlighting those over `threshold`
+///
+/// `lines` has the line count of every scanned file, under the path its findings
+/// are shown with, and directories are rolled up to `root` (shown the same way).
+pub fn print(
+    instances: &[MacroInstance],
+    lines: &BTreeMap<PathBuf, usize>,
+    root: &Path,
+    threshold: f64,
+) {
+    let mut by_file: BTreeMap<&Path, Tally> = BTreeMap::new();
+    for (file, &lines) in lines {
+        by_file.entry(file).or_default().lines = lines;
+    }
+    for instance in instances {
+        by_file.entry(&instance.path).or_default().markers += 1;
+    }
+
+    // Each file counts towards every directory above it, up to the scan root
+    let mut by_directory: BTreeMap<&Path, Tally> = BTreeMap::new();
+    let mut total = Tally::default();
+    for (file, tally) in &by_file {
+        for directory in file.ancestors().skip(1) {
+            if directory == root || !directory.starts_with(root) {
+                break;
+            }
+            let entry = by_directory.entry(directory).or_default();
+            entry.markers += tally.markers;
+            entry.lines += tally.lines;
+        }
+        total.markers += tally.markers;
+        total.lines += tally.lines;
+    }
+
+    println!(
+        "{}",
+        format!(
+            "Marker density: {:.1} per 1000 lines ({} markers in {} lines), highlighting over {}",
+            total.density(),
+            total.markers,
+            total.lines,
+            threshold
+        )
+        .yellow()
+    );
+
+    println!("{}", "Densest files:".yellow());
+    print_densest(&by_file, threshold);
+    println!("{}", "Densest directories:".yellow());
+    print_densest(&by_directory, threshold);
+}
+
+/// Print the `TOP` entries with markers, densest first
+fn print_densest(tallies: &BTreeMap<&Path, Tally>, threshold: f64) {
+    let mut densest: Vec<(&Path, Tally)> = tallies
+        .iter()
+        .filter(|(_, tally)| tally.markers > 0)
+        .map(|(&path, &tally)| (path, tally))
+        .collect();
+    densest.sort_by(|a, b| b.1.density().total_cmp(&a.1.density()).then(a.0.cmp(b.0)));
+
+    for (path, tally) in densest.into_iter().take(TOP) {
+        let density = format!("{:6.1}", tally.density());
+        let density = if tally.density() > threshold {
+            density.red().bold()
+        } else {
+            density.normal()
+        };
+        println!(
+            "  {:50} : {} ({} in {} lines)",
+            path.display(),
+            density,
+            tally.markers,
+            tally.lines
+        );
+    }
+}
@@ -0,0 +1,227 @@
+//! Marker changes between two git revisions, for the `diff` and `breaking-changes` subcommands.
+//!
+//! Findings are matched across revisions by finding ID, which leaves out the
+//! line number, so code that merely moved counts as unchanged.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use code_status_scanner::Scanner;
+use colored::Colorize;
+use rayon::prelude::*;
+use serde::Serialize;
+
+use crate::{json::Finding, MacroInstance};
+
+/// Scan the Rust files of `rev` under `root` without checking them out
+///
+/// Paths and finding IDs are the same as for a scan of the working tree.
+pub fn scan_revision(
+    scanner: &Scanner,
+    root: &Path,
+    rev: &str,
+) -> Result<Vec<MacroInstance>, String> {
+    let listing = git(root, &["ls-tree", "-r", "--name-only", rev])?;
+    let files: Vec<PathBuf> = listing
+        .lines()
+        .map(PathBuf::from)
+        .filter(|name| scanner.is_candidate(&root.join(name)))
+        .collect();
+
+    let mut instances = files
+        .par_iter()
+        .map(|name| {
+            let content = git(root, &["show", &format!("{}:./{}", rev, name.display())])?;
+            Ok(scanner.scan_source(&root.join(name), name, &content))
+        })
+        .collect::<Result<Vec<_>, String>>()?
+        .concat();
+    instances.sort_by(|a, b| a.path.cmp(&b.path).then(a.line.cmp(&b.line)));
+
+    Ok(instances)
+}
+
+/// Run git in `dir`, returning its stdout or its error message
+fn git(dir: &Path, args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .map_err(|err| format!("could not run git: {}", err))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Findings added and removed between two scans, grouped by macro
+pub struct Changes<'a> {
+    added: BTreeMap<&'a str, Vec<&'a MacroInstance>>,
+    removed: BTreeMap<&'a str, Vec<&'a MacroInstance>>,
+    unchanged: BTreeMap<&'a str, usize>,
+}
+
+/// Match `base` findings against `head` ones by ID
+///
+/// Each base finding matches at most one head finding, so adding a second
+/// identical marker still shows up as an addition.
+pub fn compare<'a>(base: &'a [MacroInstance], head: &'a [MacroInstance]) -> Changes<'a> {
+    let mut unmatched: HashMap<&str, Vec<&MacroInstance>> = HashMap::new();
+    for instance in base {
+        unmatched.entry(&instance.id).or_default().push(instance);
+    }
+
+    let mut changes = Changes {
+        added: BTreeMap::new(),
+        removed: BTreeMap::new(),
+        unchanged: BTreeMap::new(),
+    };
+    for instance in head {
+        let name = instance.macro_name.as_str();
+        if unmatched
+            .get_mut(instance.id.as_str())
+            .and_then(Vec::pop)
+            .is_some()
+        {
+            *changes.unchanged.entry(name).or_default() += 1;
+        } else {
+            changes.added.entry(name).or_default().push(instance);
+        }
+    }
+    for instance in unmatched.into_values().flatten() {
+        changes
+            .removed
+            .entry(instance.macro_name.as_str())
+            .or_default()
+            .push(instance);
+    }
+    for group in changes.removed.values_mut() {
+        group.sort_by(|a, b| a.path.cmp(&b.path).then(a.line.cmp(&b.line)));
+    }
+
+    changes
+}
+
+/// Print the changes as Markdown, ready to post as a pull request comment
+pub fn print_markdown(changes: &Changes, base: &str, head: &str) {
+    let added: usize = changes.added.values().map(Vec::len).sum();
+    let removed: usize = changes.removed.values().map(Vec::len).sum();
+    let unchanged: usize = changes.unchanged.values().sum();
+
+    println!("## Code status changes: {} → {}", base, head);
+    println!();
+    println!(
+        "**{}** added, **{}** removed, **{}** unchanged.",
+        added, removed, unchanged
+    );
+
+    for (title, groups) in [("Added", &changes.added), ("Removed", &changes.removed)] {
+        if groups.is_empty() {
+            continue;
+        }
+        println!();
+        println!("### {}", title);
+        for (macro_name, instances) in groups {
+            println!();
+            println!("#### `#[{}]` ({})", macro_name, instances.len());
+            println!();
+            for instance in instances {
+                let item = instance
+                    .item_name
+                    .as_deref()
+                    .map_or_else(String::new, |item| format!(" on `{}`", item));
+                let argument = instance
+                    .argument_value()
+                    .filter(|arg| !arg.is_empty())
+                    .map_or_else(String::new, |arg| format!(": {}", arg));
+                println!(
+                    "- `{}:{}`{}{}",
+                    instance.path.display(),
+                    instance.line,
+                    item,
+                    argument
+                );
+            }
+        }
+    }
+
+    if !changes.unchanged.is_empty() {
+        println!();
+        println!("### Unchanged");
+        println!();
+        for (macro_name, count) in &changes.unchanged {
+            println!("- `#[{}]`: {}", macro_name, count);
+        }
+    }
+}
+
+/// Print the `breaking_change` markers added since `since`, and whether they call for a major version bump
+///
+/// Markers already present at `since` shipped with that release, so they aren't listed.
+pub fn print_breaking_changes(changes: &Changes, since: &str) {
+    let added = changes
+        .added
+        .get("breaking_change")
+        .map_or(&[][..], Vec::as_slice);
+    if added.is_empty() {
+        println!(
+            "{}",
+            format!("No breaking changes since {}.", since).green()
+        );
+        return;
+    }
+
+    println!(
+        "{}",
+        format!(
+            "Breaking changes since {} ({}); the next release needs a major version bump:",
+            since,
+            added.len()
+        )
+        .red()
+        .bold()
+    );
+    println!();
+    for instance in added {
+        println!(
+            "  {} {}",
+            format!("{}:{}", instance.path.display(), instance.line).blue(),
+            instance.argument_value().unwrap_or_default()
+        );
+        println!("      {}", instance.context_block("      "));
+    }
+}
+
+/// The changes as emitted by `--format json diff`
+#[derive(Serialize)]
+struct DiffOutput<'a> {
+    base: &'a str,
+    head: &'a str,
+    added: BTreeMap<&'a str, Vec<Finding<'a>>>,
+    removed: BTreeMap<&'a str, Vec<Finding<'a>>>,
+    unchanged: &'a BTreeMap<&'a str, usize>,
+}
+
+/// Print the changes as a JSON object with findings grouped by macro
+pub fn print_json(changes: &Changes, base: &str, head: &str) {
+    crate::json::print(&DiffOutput {
+        base,
+        head,
+        added: findings(&changes.added),
+        removed: findings(&changes.removed),
+        unchanged: &changes.unchanged,
+    });
+}
+
+/// Convert grouped findings to their JSON form
+fn findings<'a>(
+    groups: &BTreeMap<&'a str, Vec<&'a MacroInstance>>,
+) -> BTreeMap<&'a str, Vec<Finding<'a>>> {
+    groups
+        .iter()
+        .map(|(name, instances)| (*name, instances.iter().map(|i| Finding::from(*i)).collect()))
+        .collect()
+}
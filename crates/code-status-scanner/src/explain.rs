@@ -0,0 +1,90 @@
+//! One-line explanations of what each macro means, for newcomers reading reports.
+
+/// A short, plain-language explanation of a macro, if it is a known one
+pub fn explanation(macro_name: &str) -> Option<&'static str> {
+    let text = match macro_name {
+        "untested" => "Marks functions that haven't been properly tested",
+        "includes_unwrap" => "Indicates code containing unwrap() calls that could panic",
+        "needs" => "Indicates a specific need (e.g., refactoring, optimization)",
+        "perf_critical" => "Marks code that needs performance optimization",
+        "security_sensitive" => "Marks code with known security implications",
+        "unsafe_usage" => "Marks code that uses unsafe blocks and needs careful auditing",
+        "no_clippy" => "Marks code where certain clippy lints are deliberately suppressed",
+        "complexity" => "Indicates high algorithm or cognitive complexity issues",
+        "allocation_heavy" => "Flags functions that perform significant heap allocations",
+        "panic_path" => "Highlights code paths that might panic under specific conditions",
+        "requires_cleanup_on_error" => "Marks code that must undo partial work when it fails",
+        "needs_review" => "Indicates code that requires special review before release",
+        "temporary" => "Marks code as temporary or intended to be replaced",
+        "assumptions" => "Indicates code with non-obvious assumptions",
+        "revisit_in" => "Marks code that may need revisiting in a future version",
+        "dependency_sensitive" => "Marks code that's sensitive to changes in dependencies",
+        "platform_specific" => "Compiles code only on the listed platforms, or documents them when `soft`",
+        "feature_gated" => "Compiles code only when one of the listed feature flags is enabled",
+        "api_stability" => "Indicates parts of the API that may change",
+        "deadlock_risk" => "Marks code with potential concurrency/deadlock issues",
+        "benchmark_candidate" => "Flags code that should be benchmarked and optimized",
+        "requires_locale" => "Flags locale-sensitive code for translation and i18n review",
+        "requires_transaction" => "Marks functions that must run inside a database transaction",
+        "requires_fuzzing" => "Flags code that should be covered by a fuzz target",
+        "requires_capacity_planning" => "Marks code whose cost scales with a deployment dimension",
+        "approximation" => "Marks numerical code that trades accuracy for speed",
+        "requires_feature_sunset" => "Marks code scheduled for removal on a given date",
+        "requires_migration" => "Marks code that depends on a pending migration",
+        "migration_complete" => "Records that a pending migration has been carried out",
+        "requires_feature_parity" => {
+            "Marks ported code that does not yet match its reference implementation"
+        },
+        "legacy_compat" => "Marks backward-compatibility shims kept for older clients",
+        "deprecated_since" => "Records the release an item was deprecated in",
+        "replaces" => "Links new code to the item it supersedes",
+        "data_race_risk" => "Marks data shared across threads without proper synchronization",
+        "requires_permission_check" => {
+            "Marks an authorization checkpoint and the permission it requires"
+        },
+        "requires_backward_compat_test" => {
+            "Flags behavior that needs a test proving older clients still work"
+        },
+        "requires_schema_migration_test" => {
+            "Flags schema changes that need a test running the migration against existing data"
+        },
+        "requires_secret" => {
+            "Records a secret the code needs at runtime, for secret-management reviews"
+        },
+        "requires_review_before" => {
+            "Sets a hard deadline for reviewing code (validated at compile time)"
+        },
+        "numeric_precision" => "Documents a precision choice (f32, f64, fixed-point) for review",
+        "requires_graceful_shutdown" => {
+            "Marks components that must shut down cleanly, e.g. by draining connections"
+        },
+        "requires_circuit_breaker" => {
+            "Marks calls to a downstream dependency that need a circuit breaker"
+        },
+        "requires_load_test" => "Flags paths that need a load test before a milestone",
+        "requires_accessibility_review" => "Flags UI code that needs an accessibility review",
+        "reviewed" => "Records that the concerns on an item were reviewed, and when and by whom (validated at compile time)",
+        "requires_chaos_test" => "Flags code that needs a chaos experiment for a failure scenario",
+        "requires_documentation_review" => "Flags public docs or examples that need review before a docs release",
+        "requires_metrics" => "Flags key paths that need metrics, like a latency histogram",
+        "observed" => "Records the metric or trace that already instruments a path",
+        "needs_benchmark_before_merge" => {
+            "Flags changes that need a benchmark run before they merge"
+        },
+        "requires_input_sanitization" => "Marks code handling untrusted input that must be sanitized against an injection class (validated at compile time)",
+        "requires_feature_documentation" => "Flags user-facing features that need release notes",
+        "requires_property_test" => "Flags serializers, parsers and other code that needs a property-based test",
+        "needs_tests" => "Records how many tests an item has out of how many it needs",
+        "owner" => "Names the team or person who owns an item (repeatable)",
+        "coverage_gap" => "Marks functions a coverage report should show as unexercised",
+        "breaking_change" => "Records an intentional breaking change to the public API",
+        "requires_rollback_plan" => "Flags changes that must not ship without a rollback plan",
+        "blocking_io" => "Marks blocking I/O, typically inside `async fn`s, to migrate to async I/O",
+        "not_thread_safe" => "Marks types that must never cross threads",
+        "requires_ordering" => {
+            "Marks code that relies on operations happening in a fixed order across threads"
+        },
+        _ => return None,
+    };
+    Some(text)
+}
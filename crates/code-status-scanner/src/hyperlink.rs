@@ -0,0 +1,71 @@
+//! OSC 8 terminal hyperlinks from finding locations to their files.
+
+use std::{fs, path::Path};
+
+/// Wrap `text` in an OSC 8 hyperlink to `path`, with `line` as the URI fragment
+///
+/// Terminals without OSC 8 support print `text` unchanged. The `#line`
+/// fragment is the convention editors opened by e.g. kitty and WezTerm use
+/// to jump to a line; other terminals just open the file.
+pub fn wrap(text: &str, path: &Path, line: usize) -> String {
+    format!(
+        "\x1b]8;;{}#{}\x1b\\{}\x1b]8;;\x1b\\",
+        file_uri(path),
+        line,
+        text
+    )
+}
+
+/// Absolute `file://` URI for `path`, percent-encoding reserved bytes
+fn file_uri(path: &Path) -> String {
+    let absolute = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let mut uri = String::from("file://");
+    if !absolute.has_root() {
+        uri.push('/');
+    }
+
+    for byte in absolute.to_string_lossy().replace('\\', "/").bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'/' | b'-' | b'_' | b'.' | b'~' | b':' => {
+                uri.push(char::from(byte));
+            },
+            _ => uri.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    uri
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrap_surrounds_text_with_an_osc8_link() {
+        let link = wrap("src/lib.rs:7", Path::new("/nonexistent/src/lib.rs"), 7);
+
+        assert_eq!(
+            link,
+            "\x1b]8;;file:///nonexistent/src/lib.rs#7\x1b\\src/lib.rs:7\x1b]8;;\x1b\\"
+        );
+    }
+
+    #[test]
+    fn file_uri_percent_encodes_reserved_bytes() {
+        assert_eq!(
+            file_uri(Path::new("/nonexistent/my project/50%/ünïcode#1.rs")),
+            "file:///nonexistent/my%20project/50%25/%C3%BCn%C3%AFcode%231.rs"
+        );
+    }
+
+    #[test]
+    fn file_uri_resolves_existing_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("a b.rs");
+        fs::write(&file, "").unwrap();
+
+        let uri = file_uri(&dir.path().join(".").join("a b.rs"));
+        assert!(uri.starts_with("file:///"), "{}", uri);
+        assert!(uri.ends_with("/a%20b.rs"), "{}", uri);
+        assert!(!uri.contains("/./"), "{}", uri);
+    }
+}
@@ -0,0 +1,133 @@
+//! Machine-readable JSON output for `--format json`.
+//!
+//! Field names are part of the scanner's interface and only change in a
+//! breaking release; see "JSON Output" in the README for the schema.
+
+use std::{
+    collections::BTreeMap,
+    io::{self, Write},
+};
+
+use serde::Serialize;
+
+use crate::{
+    reports::{self, TestBurndown},
+    severity::Severities,
+    MacroInstance, Severity,
+};
+
+/// One finding, as emitted by `list` and `search`
+#[derive(Serialize)]
+pub struct Finding<'a> {
+    id: &'a str,
+    path: String,
+    line: usize,
+    macro_name: &'a str,
+    /// The string argument without quotes, or `null` for bare markers
+    argument: Option<&'a str>,
+    context: &'a str,
+    /// Name of the annotated item, when it could be resolved
+    item: Option<&'a str>,
+    /// Kind of the annotated item, reported by `--engine ast`
+    kind: Option<&'a str>,
+    /// Who last changed the marker's line, with `--blame`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    author: Option<&'a str>,
+    /// When the marker's line was last committed, with `--blame`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    commit_date: Option<String>,
+}
+
+impl<'a> From<&'a MacroInstance> for Finding<'a> {
+    fn from(instance: &'a MacroInstance) -> Self {
+        Self {
+            id: &instance.id,
+            path: instance.path.display().to_string(),
+            line: instance.line,
+            macro_name: &instance.macro_name,
+            argument: instance.argument_value(),
+            context: instance.context.trim(),
+            item: instance.item_name.as_deref(),
+            kind: instance.item_kind,
+            author: instance.blame.as_ref().map(|b| b.author.as_str()),
+            commit_date: instance.blame.as_ref().map(|b| b.commit_date.to_string()),
+        }
+    }
+}
+
+/// Counts emitted by `summary`
+#[derive(Serialize)]
+struct Summary {
+    total: usize,
+    /// Every tier, including empty ones
+    by_severity: BTreeMap<&'static str, usize>,
+    by_macro: BTreeMap<String, usize>,
+    by_file: BTreeMap<String, usize>,
+    /// Totals of the `needs_tests` markers
+    tests_needed: TestBurndown,
+}
+
+/// Print findings as a JSON array
+pub fn print_findings<'a>(instances: impl IntoIterator<Item = &'a MacroInstance>) {
+    let findings: Vec<Finding> = instances.into_iter().map(Finding::from).collect();
+    print(&findings);
+}
+
+/// Print one finding as a single line of JSON, for `--format ndjson`
+///
+/// A reader that stops early, like `head`, ends the scan quietly.
+pub fn print_line(instance: &MacroInstance) {
+    let json = match serde_json::to_string(&Finding::from(instance)) {
+        Ok(json) => json,
+        Err(err) => {
+            eprintln!("Failed to serialize JSON output: {}", err);
+            std::process::exit(1);
+        },
+    };
+    if let Err(err) = writeln!(io::stdout().lock(), "{}", json) {
+        if err.kind() == io::ErrorKind::BrokenPipe {
+            std::process::exit(0);
+        }
+        eprintln!("Failed to write JSON output: {}", err);
+        std::process::exit(1);
+    }
+}
+
+/// Print per-macro and per-file counts as a JSON object
+pub fn print_summary(instances: &[MacroInstance], severities: &Severities) {
+    let mut summary = Summary {
+        total: instances.len(),
+        by_severity: Severity::ALL
+            .into_iter()
+            .map(|severity| (severity.as_str(), 0))
+            .collect(),
+        by_macro: BTreeMap::new(),
+        by_file: BTreeMap::new(),
+        tests_needed: reports::test_burndown(instances),
+    };
+    for instance in instances {
+        *summary
+            .by_severity
+            .entry(severities.of(&instance.macro_name).as_str())
+            .or_default() += 1;
+        *summary
+            .by_macro
+            .entry(instance.macro_name.clone())
+            .or_default() += 1;
+        *summary
+            .by_file
+            .entry(instance.path.display().to_string())
+            .or_default() += 1;
+    }
+    print(&summary);
+}
+
+pub fn print(value: &impl Serialize) {
+    match serde_json::to_string_pretty(value) {
+        Ok(json) => println!("{}", json),
+        Err(err) => {
+            eprintln!("Failed to serialize JSON output: {}", err);
+            std::process::exit(1);
+        },
+    }
+}
@@ -0,0 +1,123 @@
+//! JUnit XML output for `--format junit`, as shown in CI test panels.
+//!
+//! Each macro type is a testcase. A failing macro, Critical by default or
+//! chosen with `--junit-fail-on`, gets one failure entry per finding; other
+//! macros pass and list their findings as output.
+
+use std::collections::BTreeMap;
+
+use crate::{severity::Severities, MacroInstance, Severity};
+
+/// Print a JUnit report for `instances`
+///
+/// Failing macros among `macro_names` get a testcase even when they have no
+/// findings, so a clean run shows them passing.
+pub fn print<'a>(
+    instances: impl IntoIterator<Item = &'a MacroInstance>,
+    macro_names: &[String],
+    severities: &Severities,
+    fail_on: &[String],
+) {
+    let fails = |name: &str| {
+        if fail_on.is_empty() {
+            severities.of(name) == Severity::Critical
+        } else {
+            fail_on.iter().any(|failing| failing == name)
+        }
+    };
+
+    let mut by_macro: BTreeMap<&str, Vec<&MacroInstance>> = macro_names
+        .iter()
+        .filter(|name| fails(name))
+        .map(|name| (name.as_str(), Vec::new()))
+        .collect();
+    for instance in instances {
+        by_macro
+            .entry(&instance.macro_name)
+            .or_default()
+            .push(instance);
+    }
+
+    let failures = by_macro
+        .iter()
+        .filter(|(name, found)| fails(name) && !found.is_empty())
+        .count();
+    let tests = by_macro.len();
+
+    println!(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    println!(
+        r#"<testsuites name="code-status" tests="{}" failures="{}">"#,
+        tests, failures
+    );
+    println!(
+        r#"  <testsuite name="code-status" tests="{}" failures="{}" errors="0" skipped="0">"#,
+        tests, failures
+    );
+    for (name, found) in &by_macro {
+        if found.is_empty() {
+            println!(
+                r#"    <testcase name="{}" classname="code-status"/>"#,
+                escape(name)
+            );
+            continue;
+        }
+        println!(
+            r#"    <testcase name="{}" classname="code-status">"#,
+            escape(name)
+        );
+        if fails(name) {
+            for instance in found {
+                println!(
+                    r#"      <failure type="{}" message="{}">{}</failure>"#,
+                    escape(name),
+                    escape(&message(instance)),
+                    escape(&location(instance))
+                );
+            }
+        } else {
+            let lines: Vec<String> = found.iter().map(|instance| location(instance)).collect();
+            println!(
+                "      <system-out>{}</system-out>",
+                escape(&lines.join("\n"))
+            );
+        }
+        println!("    </testcase>");
+    }
+    println!("  </testsuite>");
+    println!("</testsuites>");
+}
+
+/// The marker's argument, or its name for bare markers
+fn message(instance: &MacroInstance) -> String {
+    match instance.argument_value() {
+        Some(argument) if !argument.is_empty() => argument.to_string(),
+        _ => format!("#[{}]", instance.macro_name),
+    }
+}
+
+/// `path:line` followed by the annotated item's signature
+fn location(instance: &MacroInstance) -> String {
+    format!(
+        "{}:{} {}",
+        instance.path.display(),
+        instance.line,
+        instance.signature().trim()
+    )
+}
+
+/// Escape text for an XML attribute or element
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c if c.is_control() && c != '\t' && c != '\n' => {},
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
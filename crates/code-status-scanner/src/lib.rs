@@ -0,0 +1,873 @@
+//! Code Status Scanner
+//!
+//! Finds code-status-macros markers in Rust sources. The `code-status-scanner`
+//! binary builds its reports on this library; other tools can embed the same
+//! scan with a [`Scanner`]:
+//!
+//! ```no_run
+//! use code_status_scanner::{Engine, Scanner};
+//! use regex::Regex;
+//!
+//! let findings = Scanner::new()
+//!     .exclude(Regex::new("tests/").unwrap())
+//!     .engine(Engine::Ast)
+//!     .scan("src".as_ref());
+//! for finding in &findings {
+//!     println!("{}:{} #[{}]", finding.path.display(), finding.line, finding.macro_name);
+//! }
+//! ```
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use code_status_core::{MACRO_NAMES, STATUS_ATTRIBUTE};
+use ignore::WalkBuilder;
+use rayon::prelude::*;
+use regex::Regex;
+use serde::Serialize;
+
+mod aliases;
+mod ast;
+pub mod blame;
+pub mod date;
+mod mask;
+mod status;
+
+/// Common directories to exclude for better performance
+pub const DEFAULT_EXCLUDE_DIRS: &[&str] = &[
+    "target/",
+    "node_modules/",
+    ".git/",
+    ".idea/",
+    ".vscode/",
+    "dist/",
+    "build/",
+];
+
+/// How files are scanned for markers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Engine {
+    /// Match attributes line by line (fast, and works on files that don't parse)
+    Regex,
+    /// Parse each file with syn and walk its items
+    Ast,
+}
+
+/// Which files under the scan path are visited
+#[derive(Debug, Clone, Copy)]
+struct WalkOptions {
+    max_depth: Option<usize>,
+    /// Skip `DEFAULT_EXCLUDE_DIRS`
+    skip_default_dirs: bool,
+    /// Honor `.gitignore`, `.ignore` and git exclude files
+    respect_gitignore: bool,
+}
+
+/// How each file's contents are scanned
+#[derive(Debug, Clone, Copy)]
+struct ScanMode {
+    engine: Engine,
+    /// Also detect macros imported under another name
+    follow_use_aliases: bool,
+    /// Lines of source kept as each finding's context, from the item's signature on
+    context_lines: usize,
+}
+
+/// Scans a directory tree for markers
+///
+/// Built up from [`Scanner::new`], which looks for every built-in marker with
+/// the same defaults as the command-line tool.
+#[derive(Debug, Clone)]
+pub struct Scanner {
+    include: Option<Regex>,
+    exclude: Option<Regex>,
+    macro_regexes: Vec<(String, Regex)>,
+    walk: WalkOptions,
+    mode: ScanMode,
+}
+
+impl Default for Scanner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Scanner {
+    /// A scanner for every macro in `MACRO_NAMES`, using the regex engine
+    pub fn new() -> Self {
+        let macro_names: Vec<String> = MACRO_NAMES.iter().map(|&name| name.to_string()).collect();
+        Self {
+            include: None,
+            exclude: None,
+            macro_regexes: create_macro_regexes(&macro_names),
+            walk: WalkOptions {
+                max_depth: None,
+                skip_default_dirs: true,
+                respect_gitignore: true,
+            },
+            mode: ScanMode {
+                engine: Engine::Regex,
+                follow_use_aliases: false,
+                context_lines: 1,
+            },
+        }
+    }
+
+    /// Only scan files whose path matches `pattern`
+    pub fn include(mut self, pattern: Regex) -> Self {
+        self.include = Some(pattern);
+        self
+    }
+
+    /// Skip files whose path matches `pattern`
+    pub fn exclude(mut self, pattern: Regex) -> Self {
+        self.exclude = Some(pattern);
+        self
+    }
+
+    /// Scan for these attribute names instead of the built-in macros
+    ///
+    /// Names outside `MACRO_NAMES`, such as markers defined in a local crate,
+    /// are matched the same way.
+    pub fn macros(mut self, macro_names: &[String]) -> Self {
+        self.macro_regexes = create_macro_regexes(macro_names);
+        self
+    }
+
+    /// How each file is scanned
+    pub fn engine(mut self, engine: Engine) -> Self {
+        self.mode.engine = engine;
+        self
+    }
+
+    /// Also detect macros imported under another name (`use code_status_macros::x as y`)
+    pub fn follow_use_aliases(mut self, follow: bool) -> Self {
+        self.mode.follow_use_aliases = follow;
+        self
+    }
+
+    /// Keep this many lines of each annotated item as the finding's context (default 1)
+    pub fn context_lines(mut self, count: usize) -> Self {
+        self.mode.context_lines = count.max(1);
+        self
+    }
+
+    /// Descend at most this many directories below the scan root
+    pub fn max_depth(mut self, depth: Option<usize>) -> Self {
+        self.walk.max_depth = depth;
+        self
+    }
+
+    /// Skip `DEFAULT_EXCLUDE_DIRS` (on by default)
+    pub fn skip_default_dirs(mut self, skip: bool) -> Self {
+        self.walk.skip_default_dirs = skip;
+        self
+    }
+
+    /// Skip files matched by `.gitignore`, `.ignore` and git exclude files (on by default)
+    pub fn respect_gitignore(mut self, respect: bool) -> Self {
+        self.walk.respect_gitignore = respect;
+        self
+    }
+
+    /// Scan every Rust file under `root`, returning findings sorted by path and line
+    pub fn scan(&self, root: &Path) -> Vec<MacroInstance> {
+        scan_directory(
+            root,
+            &self.include,
+            &self.exclude,
+            &self.macro_regexes,
+            self.walk,
+            self.mode,
+        )
+    }
+
+    /// Scan like [`Scanner::scan`], handing each file's findings to `each` as soon
+    /// as the file is done instead of collecting them all
+    ///
+    /// Files come in path order and their findings in line order, so the stream
+    /// matches what `scan` returns. A batch of files is scanned in parallel at a time.
+    pub fn scan_each(&self, root: &Path, mut each: impl FnMut(Vec<MacroInstance>)) {
+        let files = self.files(root);
+        for batch in files.chunks(rayon::current_num_threads().max(1)) {
+            let scanned: Vec<Vec<MacroInstance>> = batch
+                .par_iter()
+                .map(|file| scan_path(file, root, &self.macro_regexes, self.mode))
+                .collect();
+            for mut instances in scanned.into_iter().filter(|found| !found.is_empty()) {
+                instances.sort_by_key(|instance| instance.line);
+                each(instances);
+            }
+        }
+    }
+
+    /// Every file a scan of `root` reads, in path order
+    pub fn files(&self, root: &Path) -> Vec<PathBuf> {
+        let mut files = candidate_files(root, &self.include, &self.exclude, self.walk);
+        files.sort();
+        files
+    }
+
+    /// Whether `path` would be scanned: a `.rs` file passing the directory and pattern filters
+    ///
+    /// The depth limit is not checked, since it depends on where a walk starts.
+    pub fn is_candidate(&self, path: &Path) -> bool {
+        is_candidate(
+            path,
+            &self.include,
+            &self.exclude,
+            self.walk.skip_default_dirs,
+        )
+    }
+
+    /// Scan the contents of one file, e.g. as read from another revision
+    ///
+    /// `file` is reported as each finding's path, and `id_path` (the file's
+    /// path relative to the scan root) goes into finding IDs.
+    pub fn scan_source(&self, file: &Path, id_path: &Path, content: &str) -> Vec<MacroInstance> {
+        scan_content(file, id_path, content, &self.macro_regexes, self.mode)
+    }
+}
+
+/// Item keywords recognized when resolving the name of an annotated item
+const ITEM_KEYWORDS: &[&str] = &[
+    "fn", "struct", "enum", "trait", "type", "const", "static", "mod", "union",
+];
+
+/// Represents a found macro in the code
+#[derive(Debug, Clone, Serialize)]
+pub struct MacroInstance {
+    /// Stable content-based fingerprint, independent of the line number
+    pub id: String,
+    /// The file the marker is in, as reached from the scan root
+    pub path: PathBuf,
+    /// One-based line of the marker's attribute
+    pub line: usize,
+    /// Name of the marker, e.g. `needs`
+    pub macro_name: String,
+    /// The attribute's argument, parentheses included, as written apart from a
+    /// lone string literal being decoded (see [`MacroInstance::argument_value`])
+    pub argument: Option<String>,
+    /// Lines of the annotated item, starting at its signature
+    pub context: String,
+    /// Name of the annotated item, when it could be resolved
+    pub item_name: Option<String>,
+    /// Kind of the annotated item (`fn`, `struct`, `method`, ...), known to the AST engine only
+    pub item_kind: Option<&'static str>,
+    /// Who last changed the marker's line and when, once `blame::annotate` has run
+    pub blame: Option<blame::BlameInfo>,
+}
+
+impl MacroInstance {
+    /// The first line of the context, which is the item's signature
+    pub fn signature(&self) -> &str {
+        self.context.lines().next().unwrap_or_default()
+    }
+
+    /// The context for printing below a heading, with lines after the first
+    /// starting at `indent` too
+    pub fn context_block(&self, indent: &str) -> String {
+        self.context.trim().replace('\n', &format!("\n{}", indent))
+    }
+
+    /// Whether `other` annotates the same item as this instance
+    pub fn same_item(&self, other: &Self) -> bool {
+        self.path == other.path && self.item_name.is_some() && self.item_name == other.item_name
+    }
+
+    /// The macro argument with its surrounding parentheses and quotes removed
+    ///
+    /// A string literal argument is the string it stands for, with escapes and
+    /// raw-string delimiters resolved.
+    pub fn argument_value(&self) -> Option<&str> {
+        let arg = self.argument.as_deref()?.trim();
+        let inner = arg.strip_prefix('(')?.strip_suffix(')')?.trim();
+        Some(
+            inner
+                .strip_prefix('"')
+                .and_then(|s| s.strip_suffix('"'))
+                .unwrap_or(inner),
+        )
+    }
+
+    /// The first eight hex digits of the fingerprint, for compact display
+    pub fn short_id(&self) -> &str {
+        &self.id[..8]
+    }
+}
+
+/// Compute a finding fingerprint from its file, macro, annotated item and argument
+///
+/// The line number is deliberately excluded so that IDs survive code moving
+/// within a file. FNV-1a is used because its output is stable across
+/// platforms and Rust releases, unlike `DefaultHasher`.
+fn finding_id(file: &Path, macro_name: &str, item: &str, argument: &str) -> String {
+    let file = file.to_string_lossy().replace('\\', "/");
+    let normalized_argument = argument.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for part in [
+        file.as_str(),
+        macro_name,
+        item,
+        normalized_argument.as_str(),
+    ] {
+        for byte in part.bytes().chain(std::iter::once(0)) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    format!("{:016x}", hash)
+}
+
+/// Resolve the name of the item an attribute is attached to
+///
+/// Skips over blank lines, comments and further attributes, then takes the
+/// identifier following the first item keyword (or the header of an `impl`).
+fn find_item_name(following_lines: &[&str]) -> Option<String> {
+    let signature = following_lines
+        .iter()
+        .map(|l| l.trim())
+        .find(|l| is_item_line(l))?;
+
+    let mut tokens = signature
+        .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .filter(|t| !t.is_empty());
+
+    while let Some(token) = tokens.next() {
+        if token == "impl" {
+            let header = signature[signature.find("impl")? + 4..]
+                .split('{')
+                .next()
+                .unwrap_or_default()
+                .trim();
+            return Some(format!("impl {}", header));
+        }
+        if ITEM_KEYWORDS.contains(&token) {
+            return tokens.next().map(str::to_string);
+        }
+    }
+
+    None
+}
+
+/// Whether a line belongs to the item itself, not to an attribute or comment above it
+fn is_item_line(line: &str) -> bool {
+    let line = line.trim();
+    !line.is_empty() && !line.starts_with("#[") && !line.starts_with("//")
+}
+
+/// The first `count` of an item's `lines`, with the indentation they share removed
+fn item_context(lines: &[&str], count: usize) -> String {
+    let lines = &lines[..count.min(lines.len())];
+    let indent = lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or_default();
+
+    lines
+        .iter()
+        .map(|line| {
+            line.get(indent..)
+                .unwrap_or_else(|| line.trim_start())
+                .trim_end()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim_end()
+        .to_string()
+}
+
+/// The regex matching a `#[name(...)]` attribute, capturing the argument
+///
+/// It is matched against lines with literals blanked, so a `]` in a string
+/// doesn't end the attribute. The name must be a whole word, so `needs`
+/// doesn't match `#[needs_review]`.
+fn attribute_pattern(name: &str) -> String {
+    format!(r#"#\[{}\b([^\]]*)\]"#, regex::escape(name))
+}
+
+/// An argument as written, with a lone string literal decoded
+///
+/// Escapes and raw strings are resolved, so `(r#"say "hi""#)` is stored as
+/// `("say "hi"")` and [`MacroInstance::argument_value`] yields the string
+/// itself. Other arguments, like `needs` keys, are kept as written.
+fn decode_argument(argument: &str) -> String {
+    let literal = argument
+        .strip_prefix('(')
+        .and_then(|rest| rest.strip_suffix(')'))
+        .and_then(|inner| syn::parse_str::<syn::LitStr>(inner.trim()).ok());
+    match literal {
+        Some(literal) => format!("(\"{}\")", literal.value()),
+        None => argument.to_string(),
+    }
+}
+
+/// Pre-compile all regexes for better performance
+///
+/// `#[status(...)]` is matched too, so the markers it lists can be reported.
+fn create_macro_regexes(macro_names: &[String]) -> Vec<(String, Regex)> {
+    macro_names
+        .iter()
+        .map(String::as_str)
+        .chain([STATUS_ATTRIBUTE])
+        .map(|name| {
+            let pattern = attribute_pattern(name);
+            (
+                name.to_string(),
+                Regex::new(&pattern).expect("Failed to compile regex pattern"),
+            )
+        })
+        .collect()
+}
+
+/// Scan a directory for code status macros
+fn scan_directory(
+    path: &Path,
+    include_pattern: &Option<Regex>,
+    exclude_pattern: &Option<Regex>,
+    macro_regexes: &[(String, Regex)],
+    walk: WalkOptions,
+    mode: ScanMode,
+) -> Vec<MacroInstance> {
+    let files = candidate_files(path, include_pattern, exclude_pattern, walk);
+
+    // Files are independent, so scan them in parallel and restore a stable order after
+    let mut instances: Vec<MacroInstance> = files
+        .par_iter()
+        .flat_map_iter(|file| scan_path(file, path, macro_regexes, mode))
+        .collect();
+    instances.sort_by(|a, b| a.path.cmp(&b.path).then(a.line.cmp(&b.line)));
+
+    instances
+}
+
+/// Every file under `path` that passes the walk options and filters
+fn candidate_files(
+    path: &Path,
+    include_pattern: &Option<Regex>,
+    exclude_pattern: &Option<Regex>,
+    walk: WalkOptions,
+) -> Vec<PathBuf> {
+    // First collect all eligible files to avoid recursive regex checks
+    let walker = WalkBuilder::new(path)
+        .follow_links(true)
+        .max_depth(walk.max_depth)
+        .standard_filters(false)
+        .git_ignore(walk.respect_gitignore)
+        .git_global(walk.respect_gitignore)
+        .git_exclude(walk.respect_gitignore)
+        .ignore(walk.respect_gitignore)
+        .parents(walk.respect_gitignore)
+        .require_git(false)
+        .build()
+        .filter_map(Result::ok);
+
+    walker
+        .filter(|entry| {
+            entry.path().is_file()
+                && is_candidate(
+                    entry.path(),
+                    include_pattern,
+                    exclude_pattern,
+                    walk.skip_default_dirs,
+                )
+        })
+        .map(|entry| entry.path().to_path_buf())
+        .collect()
+}
+
+/// Whether a file path passes the `.rs`, default-directory and include/exclude filters
+fn is_candidate(
+    path: &Path,
+    include_pattern: &Option<Regex>,
+    exclude_pattern: &Option<Regex>,
+    skip_default_dirs: bool,
+) -> bool {
+    let path_str = path.to_string_lossy();
+
+    // Skip if not a Rust file
+    if !path_str.ends_with(".rs") {
+        return false;
+    }
+
+    // Skip default excluded directories if enabled
+    if skip_default_dirs
+        && DEFAULT_EXCLUDE_DIRS
+            .iter()
+            .any(|&dir| path_str.contains(dir))
+    {
+        return false;
+    }
+
+    // Apply include/exclude patterns
+    if let Some(pattern) = include_pattern {
+        if !pattern.is_match(&path_str) {
+            return false;
+        }
+    }
+
+    if let Some(pattern) = exclude_pattern {
+        if pattern.is_match(&path_str) {
+            return false;
+        }
+    }
+
+    true
+}
+
+/// Read and scan one file, returning nothing when it can't be read
+///
+/// `root` is the scan root, which finding IDs are made relative to.
+fn scan_path(
+    file: &Path,
+    root: &Path,
+    macro_regexes: &[(String, Regex)],
+    mode: ScanMode,
+) -> Vec<MacroInstance> {
+    let Ok(content) = fs::read_to_string(file) else {
+        return Vec::new();
+    };
+    let id_path = file.strip_prefix(root).unwrap_or(file);
+    scan_content(file, id_path, &content, macro_regexes, mode)
+}
+
+/// Scan one file's contents with the engine and aliases `mode` asks for
+///
+/// `id_path` is the scan-root-relative path used when fingerprinting findings.
+fn scan_content(
+    file: &Path,
+    id_path: &Path,
+    content: &str,
+    macro_regexes: &[(String, Regex)],
+    mode: ScanMode,
+) -> Vec<MacroInstance> {
+    let macro_names: Vec<&str> = macro_regexes
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .filter(|&name| name != STATUS_ATTRIBUTE)
+        .collect();
+    if mode.engine == Engine::Ast {
+        if let Some(found) = ast::scan_file(file, id_path, content, &macro_names, mode) {
+            return found;
+        }
+    }
+
+    // Aliased imports only apply to the file that declares them
+    let alias_regexes = if mode.follow_use_aliases {
+        aliases::alias_regexes(content, &macro_names)
+    } else {
+        Vec::new()
+    };
+    if alias_regexes.is_empty() {
+        scan_file(file, id_path, content, macro_regexes, mode.context_lines)
+    } else {
+        let mut file_regexes = macro_regexes.to_vec();
+        file_regexes.extend(alias_regexes);
+        scan_file(file, id_path, content, &file_regexes, mode.context_lines)
+    }
+}
+
+/// Scan a single file for code status macros
+///
+/// `id_path` is the scan-root-relative path used when fingerprinting findings,
+/// and `context_lines` how many lines of the annotated item become the context.
+fn scan_file(
+    path: &Path,
+    id_path: &Path,
+    content: &str,
+    macro_regexes: &[(String, Regex)],
+    context_lines: usize,
+) -> Vec<MacroInstance> {
+    let mut instances = Vec::new();
+    let code = mask::code_only(content);
+    let code_lines: Vec<&str> = code.lines().collect();
+    let (starts, joined) = logical_lines(content, &code_lines);
+    let lines: Vec<&str> = joined.iter().map(String::as_str).collect();
+
+    for (line_idx, line) in lines.iter().enumerate() {
+        let code_line = code_lines[starts[line_idx]];
+
+        // Every marker on the line, left to right, skipping matches inside comments and literals
+        let masked = mask::code_only(line);
+        let mut found: Vec<(&String, regex::Captures)> = macro_regexes
+            .iter()
+            .flat_map(|(macro_name, regex)| {
+                regex
+                    .captures_iter(&masked)
+                    .filter(|caps| {
+                        let start = caps.get(0).map_or(0, |m| m.start());
+                        code_line.as_bytes().get(start) == Some(&b'#')
+                    })
+                    .map(move |caps| (macro_name, caps))
+            })
+            .collect();
+        if found.is_empty() {
+            continue;
+        }
+        found.sort_by_key(|(_, caps)| caps.get(0).map_or(0, |m| m.start()));
+
+        // The context starts at the item, past any further attributes and comments,
+        // or at this line when code shares it, as with a marked statement or expression
+        let attributes_end = found
+            .iter()
+            .filter_map(|(_, caps)| caps.get(0))
+            .map(|attribute| attribute.end())
+            .max()
+            .unwrap_or_default();
+        let rest = line.get(attributes_end..).unwrap_or_default();
+        let (context, item_name) = if !line.trim_start().starts_with('#') || is_item_line(rest) {
+            (
+                item_context(&lines[line_idx..], context_lines),
+                find_item_name(&[rest]),
+            )
+        } else {
+            let following = &lines[line_idx + 1..];
+            let signature = following
+                .iter()
+                .position(|line| is_item_line(line))
+                .unwrap_or(following.len());
+            (
+                item_context(&following[signature..], context_lines),
+                find_item_name(following),
+            )
+        };
+
+        // Each attribute is reported once, by the match that starts it
+        let mut attribute_end = 0;
+        for (macro_name, caps) in found {
+            let Some(attribute) = caps.get(0) else {
+                continue;
+            };
+            if attribute.start() < attribute_end {
+                continue;
+            }
+            attribute_end = attribute.end();
+            let argument = caps.get(1).map(|m| decode_argument(line[m.range()].trim()));
+
+            // `#[status(...)]` stands for each marker it lists
+            let markers = if macro_name == STATUS_ATTRIBUTE {
+                let names: Vec<&str> = macro_regexes
+                    .iter()
+                    .map(|(name, _)| name.as_str())
+                    .filter(|&name| name != STATUS_ATTRIBUTE)
+                    .collect();
+                status::entries_from_text(argument.as_deref().unwrap_or_default(), &names)
+                    .into_iter()
+                    .map(|(name, argument)| (name, Some(argument)))
+                    .collect()
+            } else {
+                vec![(macro_name.clone(), argument)]
+            };
+
+            for (macro_name, argument) in markers {
+                let mut instance = MacroInstance {
+                    id: String::new(),
+                    path: path.to_path_buf(),
+                    line: starts[line_idx] + 1,
+                    macro_name,
+                    argument,
+                    context: context.clone(),
+                    item_name: item_name.clone(),
+                    item_kind: None,
+                    blame: None,
+                };
+                instance.id = finding_id(
+                    id_path,
+                    &instance.macro_name,
+                    instance
+                        .item_name
+                        .as_deref()
+                        .unwrap_or(instance.signature()),
+                    instance.argument_value().unwrap_or_default(),
+                );
+                instances.push(instance);
+            }
+        }
+    }
+
+    instances
+}
+
+/// Split `content` into lines, joining an attribute whose brackets span several
+/// lines into one
+///
+/// `code_lines` are the lines of `content` with comments and literals blanked,
+/// so an attribute quoted in either is not joined. Returns the zero-based
+/// source line each logical line starts on alongside the logical lines
+/// themselves.
+fn logical_lines(content: &str, code_lines: &[&str]) -> (Vec<usize>, Vec<String>) {
+    let mut starts = Vec::new();
+    let mut joined = Vec::new();
+    let mut lines = content.lines().enumerate();
+
+    while let Some((idx, line)) = lines.next() {
+        let mut logical = line.to_string();
+        let in_code = code_lines.get(idx).is_some_and(|l| attribute_is_open(l));
+        while in_code && attribute_is_open(&logical) {
+            match lines.next() {
+                Some((next_idx, next)) => {
+                    let next = code_lines
+                        .get(next_idx)
+                        .map_or(next, |code| strip_trailing_comment(next, code));
+                    join_continuation(&mut logical, next.trim());
+                },
+                None => break,
+            }
+        }
+        starts.push(idx);
+        joined.push(logical);
+    }
+
+    (starts, joined)
+}
+
+/// Drop a trailing `//` or `/* */` comment from `line`, given its blanked `code` line
+fn strip_trailing_comment<'a>(line: &'a str, code: &str) -> &'a str {
+    let code_end = code.trim_end().len();
+    match line.get(code_end..) {
+        Some(rest)
+            if rest.trim_start().starts_with("//") || rest.trim_start().starts_with("/*") =>
+        {
+            &line[..code_end]
+        },
+        _ => line,
+    }
+}
+
+/// Append a continuation line, collapsing the line break to a single space
+///
+/// No space is left inside the argument's parentheses, and a trailing comma
+/// before the closing one is dropped, so `(\n    "x",\n)` joins as `("x")`.
+fn join_continuation(logical: &mut String, next: &str) {
+    if next.starts_with(')') {
+        let trimmed = logical.trim_end().trim_end_matches(',').len();
+        logical.truncate(trimmed);
+    } else if !logical.ends_with('(') && !logical.ends_with('[') {
+        logical.push(' ');
+    }
+    logical.push_str(next);
+}
+
+/// Whether `line` starts an attribute (`#[`) without closing it
+///
+/// Brackets inside string literals, raw ones included, are ignored.
+fn attribute_is_open(line: &str) -> bool {
+    let code = mask::code_only(line);
+    let Some(attribute) = code.trim_start().strip_prefix('#') else {
+        return false;
+    };
+    if !attribute.starts_with('[') {
+        return false;
+    }
+
+    let mut depth = 0usize;
+    for c in attribute.chars() {
+        match c {
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return false;
+                }
+            },
+            _ => {},
+        }
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(source: &str) -> Vec<String> {
+        let file = Path::new("src/lib.rs");
+        Scanner::new()
+            .scan_source(file, file, source)
+            .into_iter()
+            .map(|instance| instance.id)
+            .collect()
+    }
+
+    #[test]
+    fn finding_id_survives_line_moves() {
+        let original = ids("#[needs(\"error handling\")]\nfn parse() {}\n");
+        let moved = ids("\n\n// moved down\n\n#[needs(\"error  handling\")]\nfn parse() {}\n");
+
+        assert_eq!(original.len(), 1);
+        assert_eq!(original, moved);
+    }
+
+    #[test]
+    fn finding_id_changes_with_macro_item_or_argument() {
+        let original = ids("#[needs(\"error handling\")]\nfn parse() {}\n");
+
+        for changed in [
+            "#[untested(\"error handling\")]\nfn parse() {}\n",
+            "#[needs(\"error handling\")]\nfn render() {}\n",
+            "#[needs(\"retries\")]\nfn parse() {}\n",
+        ] {
+            assert_ne!(ids(changed), original, "{}", changed);
+        }
+    }
+
+    #[test]
+    fn finding_id_depends_on_file() {
+        let source = "#[needs(\"error handling\")]\nfn parse() {}\n";
+        let other = Path::new("src/other.rs");
+        let elsewhere = Scanner::new().scan_source(other, other, source);
+
+        assert_ne!(ids(source), [elsewhere[0].id.clone()]);
+    }
+
+    #[test]
+    fn multi_line_attribute_is_reported_on_its_first_line() {
+        let source = r#"
+/// Parses the header
+#[needs(
+    "error handling"
+)]
+fn parse() {}
+
+#[needs(
+    reason = "retries", // backoff too
+    issue = "PROJ-7"
+)]
+fn fetch() {}
+"#;
+        let file = Path::new("src/lib.rs");
+
+        for engine in [Engine::Regex, Engine::Ast] {
+            let found: Vec<_> = Scanner::new()
+                .engine(engine)
+                .scan_source(file, file, source)
+                .into_iter()
+                .map(|i| (i.line, i.argument_value().map(str::to_string), i.item_name))
+                .collect();
+            assert_eq!(
+                found,
+                [
+                    (
+                        3,
+                        Some("error handling".to_string()),
+                        Some("parse".to_string())
+                    ),
+                    (
+                        8,
+                        Some(r#"reason = "retries", issue = "PROJ-7""#.to_string()),
+                        Some("fetch".to_string())
+                    )
+                ],
+                "{:?}",
+                engine
+            );
+        }
+    }
+}
@@ -3,52 +3,42 @@
 //! A CLI tool to scan Rust codebases for code-status-macros usage and generate reports.
 
 use std::{
-    collections::HashMap,
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap, HashSet},
     fs,
+    io::{self, IsTerminal},
     path::{Path, PathBuf},
+    process,
 };
 
 use clap::{Parser, Subcommand};
 use colored::Colorize;
 use regex::Regex;
-use walkdir::WalkDir;
-
-/// List of all macros from code-status-macros
-const MACRO_NAMES: &[&str] = &[
-    // Code Quality Markers
-    "untested",
-    "includes_unwrap",
-    "needs",
-    "perf_critical",
-    "security_sensitive",
-    "unsafe_usage",
-    "no_clippy",
-    "complexity",
-    "allocation_heavy",
-    "panic_path",
-    // Review & Future Work Markers
-    "needs_review",
-    "temporary",
-    "assumptions",
-    "revisit_in",
-    "dependency_sensitive",
-    "platform_specific",
-    "feature_gated",
-    "api_stability",
-    "deadlock_risk",
-    "benchmark_candidate",
-];
-
-/// Common directories to exclude for better performance
-const DEFAULT_EXCLUDE_DIRS: &[&str] = &[
-    "target/",
-    "node_modules/",
-    ".git/",
-    ".idea/",
-    ".vscode/",
-    "dist/",
-    "build/",
-];
+
+mod baseline;
+mod config;
+mod coverage;
+mod density;
+mod diff;
+mod explain;
+mod hyperlink;
+mod json;
+mod junit;
+mod markdown;
+mod release;
+mod reports;
+mod review;
+mod sarif;
+mod severity;
+mod stale;
+mod transactions;
+mod validate;
+mod watch;
+
+use code_status_core::MACRO_NAMES;
+use code_status_scanner::{blame, date, Engine, MacroInstance, Scanner};
+use date::Date;
+use severity::{Severities, Severity};
 
 /// CLI arguments
 #[derive(Parser)]
@@ -68,21 +58,324 @@ struct Cli {
     exclude: Option<String>,
 
     /// Maximum directory depth to scan (default: no limit)
-    #[arg(short, long)]
+    #[arg(short = 'd', long)]
     max_depth: Option<usize>,
 
     /// Skip default excluded directories (target/, node_modules/, etc.)
     #[arg(short = 'S', long, default_value_t = true)]
     skip_default_dirs: bool,
 
+    /// Scan files ignored by `.gitignore`, `.ignore` and git exclude files too
+    #[arg(long)]
+    no_gitignore: bool,
+
+    /// Ignore one macro under a path prefix, as `macro:path/prefix` (repeatable)
+    #[arg(long, value_name = "MACRO:PATH", value_parser = parse_macro_path_rule)]
+    exclude_macro_in_path: Vec<MacroPathRule>,
+
+    /// Also scan for these attribute names, such as markers defined in a local crate (comma-separated or repeated)
+    #[arg(long, value_name = "NAME", value_delimiter = ',', value_parser = parse_macro_name)]
+    extra_macros: Vec<String>,
+
+    /// Only scan for these macros (comma-separated or repeated)
+    #[arg(long, value_name = "MACRO", value_delimiter = ',', value_parser = parse_macro_name)]
+    only: Vec<String>,
+
+    /// Also detect macros imported under another name (`use code_status_macros::x as y`)
+    #[arg(long)]
+    follow_use_aliases: bool,
+
+    /// Don't report findings recorded in this baseline file (written by `baseline`)
+    #[arg(long, value_name = "FILE")]
+    baseline: Option<PathBuf>,
+
+    /// Attach the author and commit date of each marker's line from `git blame`
+    #[arg(long)]
+    blame: bool,
+
+    /// Only show open concerns, hiding those on items marked `#[reviewed]`
+    #[arg(long)]
+    open_only: bool,
+
+    /// Only report findings at least this severe: critical, warning or info
+    #[arg(long, value_name = "LEVEL", value_parser = parse_severity)]
+    min_severity: Option<Severity>,
+
+    /// Read project settings from this file instead of `code-status.toml` in the scan path
+    #[arg(long, value_name = "FILE")]
+    config: Option<PathBuf>,
+
+    /// Exit with code 1 when there are more than this many findings (only the searched macros with `search`)
+    #[arg(long, value_name = "COUNT")]
+    fail_over: Option<usize>,
+
+    /// Exit with code 1 when any finding of this macro exists (repeatable)
+    #[arg(long, value_name = "MACRO", value_parser = parse_macro_name)]
+    fail_on: Vec<String>,
+
+    /// Number of threads to scan files with (default: one per CPU)
+    #[arg(short = 'j', long, value_name = "N")]
+    jobs: Option<usize>,
+
+    /// Lines of source to show under each finding, starting at the annotated item's signature
+    #[arg(long, value_name = "N", default_value_t = 1, value_parser = parse_context_lines)]
+    context_lines: usize,
+
+    /// How files are scanned; `ast` resolves each marker's item and kind, falling back to `regex` for files that don't parse
+    #[arg(long, value_enum, default_value_t = Engine::Regex)]
+    engine: Engine,
+
+    /// Output format; `json` and `markdown` are supported by `list`, `summary`, `search` and `diff`, `sarif`, `ndjson` and `junit` by `list` and `search`
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    format: OutputFormat,
+
+    /// Override a macro's SARIF level, as `macro=error|warning|note` (repeatable)
+    #[arg(long, value_name = "MACRO=LEVEL", value_parser = parse_sarif_level)]
+    sarif_level: Vec<(String, sarif::Level)>,
+
+    /// Macros reported as JUnit failures, instead of the Critical ones (comma-separated or repeated)
+    #[arg(long, value_name = "MACRO", value_delimiter = ',', value_parser = parse_macro_name)]
+    junit_fail_on: Vec<String>,
+
+    /// Order findings by path (then line), line, macro name, or severity (most severe first)
+    #[arg(long, value_enum, value_name = "KEY", default_value_t = SortKey::Path)]
+    sort: SortKey,
+
+    /// Reverse the `--sort` order, and the macro counts in `summary`
+    #[arg(long)]
+    reverse: bool,
+
+    /// Show absolute file paths instead of paths relative to the scan path
+    #[arg(long)]
+    absolute: bool,
+
+    /// Don't make listed file paths clickable (OSC 8 links are only emitted on a terminal)
+    #[arg(long)]
+    no_hyperlinks: bool,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
 
+/// How reports are rendered
+#[derive(Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum OutputFormat {
+    /// Colored, human-readable text
+    Text,
+    /// JSON for machine consumption
+    Json,
+    /// SARIF 2.1.0, for GitHub code scanning
+    Sarif,
+    /// Markdown tables and lists, for pull request comments and wikis
+    Markdown,
+    /// One JSON object per line, written as files are scanned
+    Ndjson,
+    /// JUnit XML, for CI test panels
+    Junit,
+}
+
+/// How findings are ordered, each key falling back to path and line
+#[derive(Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+enum SortKey {
+    Path,
+    Line,
+    Macro,
+    Severity,
+}
+
 #[derive(Subcommand)]
 enum Commands {
     /// List all macros found in the codebase
-    List,
+    List {
+        /// Group findings under Critical, Warning and Info headers
+        #[arg(long)]
+        by_severity: bool,
+
+        /// Explain each macro type the first time it appears
+        #[arg(long)]
+        with_explanations: bool,
+
+        /// Group findings with the same macro and argument under one header with a count
+        #[arg(long, conflicts_with = "by_severity")]
+        collapse: bool,
+
+        /// Also show Info findings, which are hidden from text output by default
+        #[arg(long)]
+        all: bool,
+    },
+    /// Generate a summary report of macro usage
+    Summary {
+        /// Only count concurrency markers (`deadlock_risk`, `data_race_risk`, `not_thread_safe`, `blocking_io`, `requires_ordering`)
+        #[arg(long)]
+        concurrency: bool,
+
+        /// Also show markers per 1000 lines for the densest files and directories
+        #[arg(long)]
+        density: bool,
+
+        /// Highlight files and directories with more markers per 1000 lines than this
+        #[arg(
+            long,
+            value_name = "PER_1000",
+            default_value_t = 10.0,
+            requires = "density"
+        )]
+        density_threshold: f64,
+    },
+    /// Search for specific macros
+    Search {
+        /// Macros to search for (comma-separated)
+        #[arg(required = true)]
+        macros: String,
+    },
+    /// List code scheduled for removal via `requires_feature_sunset`, ordered by date
+    Sunsets,
+    /// Check marker consistency rules, exiting non-zero when any are violated
+    Validate,
+    /// List ported code still pending parity via `requires_feature_parity`
+    Parity,
+    /// List backward-compatibility shims marked `legacy_compat` with their deprecation and planned removal
+    CompatSurface,
+    /// Group `requires_capacity_planning` markers by their scaling dimension
+    Capacity,
+    /// List `replaces` pairs and whether each replaced item is marked for removal
+    Migration,
+    /// Report `requires_fuzzing` items that no fuzz target references by name
+    FuzzGaps {
+        /// Directory holding fuzz targets, relative to the scan path
+        #[arg(long, default_value = "fuzz/fuzz_targets")]
+        fuzz_dir: PathBuf,
+    },
+    /// List locale-sensitive code marked `requires_locale` for i18n review
+    I18n,
+    /// Group error-path concerns per function, flagging panics that skip required cleanup
+    ErrorHandling,
+    /// List `approximation` markers for a numerical-correctness review
+    Numerics,
+    /// Report concurrency hazards (`deadlock_risk`, `data_race_risk`, `not_thread_safe`, `blocking_io`, `requires_ordering`) grouped by type
+    Concurrency,
+    /// Group `requires_permission_check` markers by permission and list unguarded sensitive code
+    Authz,
+    /// Report `requires_backward_compat_test` items that no test file references by name
+    CompatTests {
+        /// Directory holding test files, relative to the scan path
+        #[arg(long, default_value = "tests")]
+        tests_dir: PathBuf,
+    },
+    /// Score release readiness from unresolved release-blocking markers
+    ReleaseReadiness {
+        /// Override a marker's penalty as `macro=points` (repeatable)
+        #[arg(long, value_name = "MACRO=POINTS", value_parser = release::parse_penalty)]
+        penalty: Vec<release::Penalty>,
+
+        /// Lowest score that still passes
+        #[arg(long, default_value_t = 100)]
+        min_score: u32,
+    },
+    /// Inventory secrets named by `requires_secret`, deduplicated, with their usage sites
+    Secrets,
+    /// Count markers per owner and macro, from `owner` markers or `--blame` authors
+    Owners,
+    /// Fail when any `requires_review_before` deadline has passed
+    ReviewGate {
+        /// Evaluate deadlines as of this date (`YYYY-MM-DD`) instead of today
+        #[arg(long, value_parser = parse_date)]
+        today: Option<Date>,
+    },
+    /// List `numeric_precision` decisions grouped by numeric type for a precision audit
+    Precision,
+    /// Print only the number of findings, for scripts
+    Count {
+        /// Only count these macros (comma-separated or repeated)
+        #[arg(long = "macro", value_name = "MACRO", value_delimiter = ',', value_parser = parse_macro_name)]
+        macros: Vec<String>,
+
+        /// Only count findings of this severity: critical, warning or info
+        #[arg(long, value_parser = parse_severity)]
+        severity: Option<Severity>,
+    },
+    /// List components marked `requires_graceful_shutdown` for a resilience review
+    Shutdown,
+    /// Group `requires_circuit_breaker` markers by dependency, cross-referenced with `dependency_sensitive`
+    Resilience,
+    /// List paths still needing a load test, grouped by milestone
+    LoadTestGate,
+    /// List UI code marked `requires_accessibility_review`
+    A11y,
+    /// Group `requires_chaos_test` markers by failure scenario
+    Chaos,
+    /// List documentation marked `requires_documentation_review`, e.g. before a docs release
+    DocsReview,
+    /// List metrics requirements and `perf_critical` paths with neither `observed` nor `requires_metrics`
+    Observability,
+    /// Group `requires_input_sanitization` markers by injection class
+    InjectionSurface,
+    /// Report `requires_feature_documentation` items the changelog doesn't mention
+    ReleaseNotesGaps {
+        /// Changelog to check, relative to the scan path
+        #[arg(long, default_value = "CHANGELOG.md")]
+        changelog: PathBuf,
+    },
+    /// Report `requires_property_test` items that no proptest or quickcheck test references by name
+    PropertyTests {
+        /// Directory holding test files, relative to the scan path
+        #[arg(long, default_value = "tests")]
+        tests_dir: PathBuf,
+    },
+    /// Fail when a `revisit_in` date or version has been reached
+    Stale {
+        /// Version to check version deadlines against, e.g. `2.1.0`
+        #[arg(long, value_parser = parse_version)]
+        current_version: Option<stale::Version>,
+
+        /// Evaluate date deadlines as of this date (`YYYY-MM-DD`) instead of today
+        #[arg(long, value_parser = parse_date)]
+        today: Option<Date>,
+    },
+    /// Record every current finding in a baseline file for `--baseline`
+    Baseline {
+        /// File to write
+        #[arg(long, default_value = "code-status-baseline.json")]
+        output: PathBuf,
+    },
+    /// Compare findings between two git revisions, grouped by macro, as Markdown or JSON
+    Diff {
+        /// Revision to compare from, e.g. `origin/main`
+        #[arg(long)]
+        base: String,
+
+        /// Revision to compare to (default: the working tree)
+        #[arg(long)]
+        head: Option<String>,
+    },
+    /// Check `coverage_gap` and `untested` functions against an LCOV coverage report
+    Coverage {
+        /// LCOV file, as written by `cargo llvm-cov --lcov`, relative to the scan path
+        #[arg(long, value_name = "FILE", default_value = "lcov.info")]
+        lcov: PathBuf,
+    },
+    /// List `breaking_change` markers added since a git tag, to decide on a major version bump
+    BreakingChanges {
+        /// Tag (or other revision) of the last release, e.g. `v1.2.0`
+        #[arg(long, value_name = "TAG")]
+        since: String,
+    },
+    /// Rerun a report whenever a scanned file changes (default: `list`)
+    Watch {
+        #[command(subcommand)]
+        report: Option<WatchedReport>,
+    },
+}
+
+/// Reports `watch` can rerun
+#[derive(Subcommand, Clone)]
+enum WatchedReport {
+    /// List all macros found in the codebase
+    List {
+        /// Also show Info findings
+        #[arg(long)]
+        all: bool,
+    },
     /// Generate a summary report of macro usage
     Summary,
     /// Search for specific macros
@@ -93,56 +386,353 @@ enum Commands {
     },
 }
 
-/// Represents a found macro in the code
-#[derive(Debug)]
-struct MacroInstance {
-    path: PathBuf,
-    line: usize,
+/// Drops a single macro when it is found under a path prefix
+#[derive(Debug, Clone)]
+struct MacroPathRule {
     macro_name: String,
-    argument: Option<String>,
-    context: String,
+    path_prefix: String,
 }
 
-/// Pre-compile all regexes for better performance
-fn create_macro_regexes() -> Vec<(String, Regex)> {
-    MACRO_NAMES
-        .iter()
-        .map(|&name| {
-            let pattern = format!("#\\[{}(.*?)\\]", name);
-            (
-                name.to_string(),
-                Regex::new(&pattern).expect("Failed to compile regex pattern"),
-            )
-        })
-        .collect()
+impl MacroPathRule {
+    /// Whether this rule suppresses `instance`, given its scan-root-relative path
+    fn matches(&self, instance: &MacroInstance, relative_path: &Path) -> bool {
+        instance.macro_name == self.macro_name
+            && relative_path
+                .to_string_lossy()
+                .replace('\\', "/")
+                .starts_with(&self.path_prefix)
+    }
+}
+
+/// Parse a `macro:path/prefix` rule for `--exclude-macro-in-path`
+fn parse_macro_path_rule(rule: &str) -> Result<MacroPathRule, String> {
+    let (macro_name, path_prefix) = rule
+        .split_once(':')
+        .ok_or_else(|| format!("expected `macro:path/prefix`, got `{}`", rule))?;
+    let macro_name = parse_macro_name(macro_name)?;
+    let path_prefix = path_prefix.trim();
+
+    if path_prefix.is_empty() {
+        return Err(format!("missing path prefix in `{}`", rule));
+    }
+
+    Ok(MacroPathRule {
+        macro_name,
+        path_prefix: path_prefix.trim_start_matches("./").replace('\\', "/"),
+    })
+}
+
+/// Accept a macro name on the command line
+///
+/// Only the syntax is checked here; whether the macro is known depends on the
+/// extra macros, so `check_macro_names` does that once they are loaded.
+fn parse_macro_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if is_attribute_name(name) {
+        Ok(name.to_string())
+    } else {
+        Err(format!("`{}` is not a valid attribute name", name))
+    }
+}
+
+/// Whether `name` is a plain identifier that can name an attribute macro
+fn is_attribute_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && name != "_"
+}
+
+/// Parse a severity tier name
+fn parse_severity(name: &str) -> Result<Severity, String> {
+    name.parse()
+}
+
+/// Parse a `macro=level` SARIF level override
+fn parse_sarif_level(rule: &str) -> Result<(String, sarif::Level), String> {
+    let (macro_name, level) = rule
+        .split_once('=')
+        .ok_or_else(|| format!("expected `macro=level`, got `{}`", rule))?;
+    let level = <sarif::Level as clap::ValueEnum>::from_str(level.trim(), true)
+        .map_err(|_| format!("expected error, warning or note, got `{}`", level.trim()))?;
+    Ok((parse_macro_name(macro_name)?, level))
+}
+
+/// Parse a `--context-lines` count, which must be at least 1
+fn parse_context_lines(value: &str) -> Result<usize, String> {
+    match value.parse() {
+        Ok(0) | Err(_) => Err(format!(
+            "expected a number of lines of 1 or more, got `{}`",
+            value
+        )),
+        Ok(count) => Ok(count),
+    }
+}
+
+/// Parse a `YYYY-MM-DD` command-line date
+fn parse_date(value: &str) -> Result<Date, String> {
+    Date::parse(value).ok_or_else(|| format!("expected a YYYY-MM-DD date, got `{}`", value))
+}
+
+/// Parse a command-line version such as `2.1.0` or `v2.1`
+fn parse_version(value: &str) -> Result<stale::Version, String> {
+    stale::Version::parse(value)
+        .ok_or_else(|| format!("expected a version like 2.1.0, got `{}`", value))
 }
 
 fn main() {
     let cli = Cli::parse();
     let path = Path::new(&cli.path);
+    // Finding paths are shown relative to this directory
+    let root = scan_root(path);
+
+    if cli.format == OutputFormat::Json {
+        if !matches!(
+            cli.command,
+            None | Some(
+                Commands::List { .. }
+                    | Commands::Summary { .. }
+                    | Commands::Search { .. }
+                    | Commands::Diff { .. }
+            )
+        ) {
+            eprintln!("--format json is only supported by list, summary, search and diff");
+            process::exit(2);
+        }
+        colored::control::set_override(false);
+    }
+    if cli.format == OutputFormat::Markdown {
+        if !matches!(
+            cli.command,
+            None | Some(
+                Commands::List { .. }
+                    | Commands::Summary { .. }
+                    | Commands::Search { .. }
+                    | Commands::Diff { .. }
+            )
+        ) {
+            eprintln!("--format markdown is only supported by list, summary, search and diff");
+            process::exit(2);
+        }
+        colored::control::set_override(false);
+    }
+    if cli.format == OutputFormat::Sarif {
+        if !matches!(
+            cli.command,
+            None | Some(Commands::List { .. } | Commands::Search { .. })
+        ) {
+            eprintln!("--format sarif is only supported by list and search");
+            process::exit(2);
+        }
+        colored::control::set_override(false);
+    }
+    if cli.format == OutputFormat::Junit {
+        if !matches!(
+            cli.command,
+            None | Some(Commands::List { .. } | Commands::Search { .. })
+        ) {
+            eprintln!("--format junit is only supported by list and search");
+            process::exit(2);
+        }
+        colored::control::set_override(false);
+    }
+    if cli.format != OutputFormat::Text
+        && matches!(cli.command, Some(Commands::Summary { density: true, .. }))
+    {
+        eprintln!("summary --density is only supported with --format text");
+        process::exit(2);
+    }
+    if cli.format == OutputFormat::Ndjson {
+        if !matches!(
+            cli.command,
+            None | Some(Commands::List { .. } | Commands::Search { .. })
+        ) {
+            eprintln!("--format ndjson is only supported by list and search");
+            process::exit(2);
+        }
+        // Findings are written before the whole scan is known
+        if cli.sort != SortKey::Path || cli.reverse || cli.baseline.is_some() {
+            eprintln!("--format ndjson streams findings in path order and can't be combined with --sort, --reverse or --baseline");
+            process::exit(2);
+        }
+        colored::control::set_override(false);
+    }
 
     // Compile regex patterns if provided
-    let include_pattern = cli
-        .pattern
-        .map(|p| Regex::new(&p).expect("Invalid include pattern"));
-    let exclude_pattern = cli
-        .exclude
-        .map(|p| Regex::new(&p).expect("Invalid exclude pattern"));
+    let mut scanner = Scanner::new();
+    if let Some(pattern) = &cli.pattern {
+        scanner = scanner.include(Regex::new(pattern).expect("Invalid include pattern"));
+    }
+    if let Some(pattern) = &cli.exclude {
+        scanner = scanner.exclude(Regex::new(pattern).expect("Invalid exclude pattern"));
+    }
+
+    let config =
+        config::load(cli.config.as_deref(), path, &cli.extra_macros).unwrap_or_else(|err| {
+            eprintln!("Failed to read config {}", err);
+            process::exit(2);
+        });
+    let severities = &config.severities;
+
+    // Built-in macros, then extra ones from the config file and the command line
+    let mut known_macros: Vec<String> = MACRO_NAMES.iter().map(|&name| name.to_string()).collect();
+    for name in config.extra_macros.iter().chain(&cli.extra_macros) {
+        if !known_macros.contains(name) {
+            known_macros.push(name.clone());
+        }
+    }
+    check_macro_names(&cli, &known_macros);
+    let scanned_macros: Vec<String> = known_macros
+        .into_iter()
+        .filter(|name| cli.only.is_empty() || cli.only.contains(name))
+        .collect();
+
+    if let Some(jobs) = cli.jobs {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(jobs)
+            .build_global()
+            .expect("Failed to configure the scan thread pool");
+    }
 
-    // Pre-compile all the regexes we'll need
-    let macro_regexes = create_macro_regexes();
+    let scanner = scanner
+        .macros(&scanned_macros)
+        .engine(cli.engine)
+        .follow_use_aliases(cli.follow_use_aliases)
+        .context_lines(cli.context_lines)
+        .max_depth(cli.max_depth)
+        .skip_default_dirs(cli.skip_default_dirs)
+        .respect_gitignore(!cli.no_gitignore);
+
+    if cli.format == OutputFormat::Ndjson {
+        let searched: Option<Vec<&str>> = match &cli.command {
+            Some(Commands::Search { macros }) => Some(macros.split(',').map(str::trim).collect()),
+            _ => None,
+        };
+        // Only counts are kept for the thresholds, not the findings themselves
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        scanner.scan_each(path, |mut found| {
+            drop_excluded(&cli, root, severities, &mut found);
+            if let Some(names) = &searched {
+                found.retain(|i| names.contains(&i.macro_name.as_str()));
+            }
+            if cli.blame && !cli.open_only {
+                blame::annotate(&mut found);
+            }
+            show_paths(&mut found, root, cli.absolute);
+            for instance in &found {
+                json::print_line(instance);
+                *counts.entry(instance.macro_name.clone()).or_default() += 1;
+            }
+        });
+        check_counts(&cli, &counts);
+        return;
+    }
+
+    if let Some(Commands::Watch { report }) = &cli.command {
+        let report = report.clone().unwrap_or(WatchedReport::List { all: false });
+        let hyperlinks = (!cli.no_hyperlinks && io::stdout().is_terminal()).then_some(root);
+        let watched = watch::run(
+            path,
+            |changed| scanner.is_candidate(changed),
+            || {
+                let mut instances = scanner.scan(path);
+                drop_excluded(&cli, root, severities, &mut instances);
+                show_paths(&mut instances, root, cli.absolute);
+                sort_instances(&mut instances, cli.sort, cli.reverse, severities);
+                match &report {
+                    WatchedReport::List { all } => {
+                        let (shown, hidden) = hide_info(&instances, *all, severities);
+                        list_macros(&shown, false, hyperlinks);
+                        print_hidden_footer(hidden, "--all");
+                    },
+                    WatchedReport::Summary => {
+                        generate_summary(&instances, severities, cli.sort, cli.reverse);
+                    },
+                    WatchedReport::Search { macros } => search_macros(&instances, macros),
+                }
+            },
+        );
+        if let Err(err) = watched {
+            eprintln!("Failed to watch {}", err);
+            process::exit(2);
+        }
+        return;
+    }
 
     // Find all macros in the codebase
-    let instances = scan_directory(
-        path,
-        &include_pattern,
-        &exclude_pattern,
-        &macro_regexes,
-        cli.max_depth,
-        cli.skip_default_dirs,
-    );
+    let mut instances = scanner.scan(path);
+    drop_excluded(&cli, root, severities, &mut instances);
+
+    // `diff` and `breaking-changes` compare against other revisions instead of
+    // reporting findings on their own
+    let scan_revision = |rev: &str| {
+        let mut found = diff::scan_revision(&scanner, path, rev).unwrap_or_else(|err| {
+            eprintln!("Failed to scan revision {}: {}", rev, err);
+            process::exit(2);
+        });
+        drop_excluded(&cli, root, severities, &mut found);
+        show_paths(&mut found, root, cli.absolute);
+        found
+    };
+    if let Some(Commands::BreakingChanges { since }) = &cli.command {
+        let base_instances = scan_revision(since);
+        show_paths(&mut instances, root, cli.absolute);
+        diff::print_breaking_changes(&diff::compare(&base_instances, &instances), since);
+        return;
+    }
+    if let Some(Commands::Diff { base, head }) = &cli.command {
+        let base_instances = scan_revision(base);
+        show_paths(&mut instances, root, cli.absolute);
+        let head_instances = head.as_deref().map_or(instances, scan_revision);
+        let head_label = head.as_deref().unwrap_or("working tree");
+
+        let changes = diff::compare(&base_instances, &head_instances);
+        if cli.format == OutputFormat::Json {
+            diff::print_json(&changes, base, head_label);
+        } else {
+            diff::print_markdown(&changes, base, head_label);
+        }
+        return;
+    }
+
+    // Findings recorded in a baseline aren't reported, except when writing a new one
+    if let Some(file) = &cli.baseline {
+        if !matches!(cli.command, Some(Commands::Baseline { .. })) {
+            match baseline::load(file) {
+                Ok(entries) => baseline::print_stale(&baseline::subtract(&mut instances, entries)),
+                Err(err) => {
+                    eprintln!("Failed to read baseline {}", err);
+                    process::exit(2);
+                },
+            }
+        }
+    }
+
+    // `--open-only` has attached blame already, in `drop_excluded`
+    if cli.blame && !cli.open_only {
+        blame::annotate(&mut instances);
+    }
+
+    show_paths(&mut instances, root, cli.absolute);
+    sort_instances(&mut instances, cli.sort, cli.reverse, severities);
+
+    if let Some(Commands::Summary {
+        concurrency: true, ..
+    }) = &cli.command
+    {
+        instances.retain(|i| reports::CONCURRENCY_MARKERS.contains(&i.macro_name.as_str()));
+    }
 
-    if instances.is_empty() {
+    // `count` prints a bare 0 and JSON an empty result instead of the message below
+    if instances.is_empty()
+        && cli.format == OutputFormat::Text
+        && !matches!(
+            cli.command,
+            Some(Commands::Count { .. } | Commands::Baseline { .. })
+        )
+    {
         println!(
             "{}",
             "No code status macros found in the codebase.".yellow()
@@ -150,157 +740,685 @@ fn main() {
         return;
     }
 
+    let hyperlinks = (!cli.no_hyperlinks && io::stdout().is_terminal()).then_some(root);
+
+    if cli.format == OutputFormat::Json {
+        match &cli.command {
+            Some(Commands::Summary { .. }) => json::print_summary(&instances, severities),
+            Some(Commands::Search { macros }) => {
+                let macro_names: Vec<&str> = macros.split(',').map(str::trim).collect();
+                json::print_findings(
+                    instances
+                        .iter()
+                        .filter(|i| macro_names.contains(&i.macro_name.as_str())),
+                );
+            },
+            _ => json::print_findings(&instances),
+        }
+        check_thresholds(&cli, &instances);
+        return;
+    }
+
+    if cli.format == OutputFormat::Markdown {
+        match &cli.command {
+            Some(Commands::Summary { .. }) => markdown::print_summary(&instances, severities),
+            Some(Commands::Search { macros }) => {
+                let macro_names: Vec<&str> = macros.split(',').map(str::trim).collect();
+                let found: Vec<_> = instances
+                    .iter()
+                    .filter(|i| macro_names.contains(&i.macro_name.as_str()))
+                    .collect();
+                markdown::print_findings(&found);
+            },
+            _ => markdown::print_findings(&instances.iter().collect::<Vec<_>>()),
+        }
+        check_thresholds(&cli, &instances);
+        return;
+    }
+
+    if cli.format == OutputFormat::Sarif {
+        if let Some(Commands::Search { macros }) = &cli.command {
+            let macro_names: Vec<&str> = macros.split(',').map(str::trim).collect();
+            sarif::print(
+                instances
+                    .iter()
+                    .filter(|i| macro_names.contains(&i.macro_name.as_str())),
+                &scanned_macros,
+                severities,
+                &cli.sarif_level,
+            );
+        } else {
+            sarif::print(&instances, &scanned_macros, severities, &cli.sarif_level);
+        }
+        check_thresholds(&cli, &instances);
+        return;
+    }
+
+    if cli.format == OutputFormat::Junit {
+        if let Some(Commands::Search { macros }) = &cli.command {
+            let macro_names: Vec<&str> = macros.split(',').map(str::trim).collect();
+            let searched: Vec<String> = scanned_macros
+                .iter()
+                .filter(|name| macro_names.contains(&name.as_str()))
+                .cloned()
+                .collect();
+            junit::print(
+                instances
+                    .iter()
+                    .filter(|i| macro_names.contains(&i.macro_name.as_str())),
+                &searched,
+                severities,
+                &cli.junit_fail_on,
+            );
+        } else {
+            junit::print(&instances, &scanned_macros, severities, &cli.junit_fail_on);
+        }
+        check_thresholds(&cli, &instances);
+        return;
+    }
+
     match &cli.command {
-        Some(Commands::List) => list_macros(&instances),
-        Some(Commands::Summary) => generate_summary(&instances),
+        Some(Commands::List {
+            by_severity,
+            with_explanations,
+            collapse,
+            all,
+        }) => {
+            let (shown, hidden) = hide_info(&instances, *all, severities);
+            if *collapse {
+                list_collapsed(&shown, *with_explanations, hyperlinks);
+            } else if *by_severity {
+                list_by_severity(&shown, *with_explanations, hyperlinks, severities);
+            } else {
+                list_macros(&shown, *with_explanations, hyperlinks);
+            }
+            print_hidden_footer(hidden, "--all");
+        },
+        Some(Commands::Summary {
+            density,
+            density_threshold,
+            ..
+        }) => {
+            generate_summary(&instances, severities, cli.sort, cli.reverse);
+            if *density {
+                let mut lines = BTreeMap::new();
+                let absolute_root = absolute_root(root, cli.absolute);
+                for file in scanner.files(path) {
+                    if let Some(shown) = shown_path(&file, root, absolute_root.as_deref()) {
+                        lines.insert(shown, density::count_lines(&file));
+                    }
+                }
+                println!();
+                let shown_root = absolute_root.as_deref().unwrap_or(Path::new(""));
+                density::print(&instances, &lines, shown_root, *density_threshold);
+            }
+        },
         Some(Commands::Search { macros }) => search_macros(&instances, macros),
-        None => list_macros(&instances), // Default to list if no subcommand provided
+        Some(Commands::Sunsets) => reports::list_sunsets(&instances, Date::today()),
+        Some(Commands::Parity) => reports::list_marker(
+            &instances,
+            "requires_feature_parity",
+            "items pending feature parity",
+        ),
+        Some(Commands::CompatSurface) => reports::compat_surface(&instances),
+        Some(Commands::Capacity) => reports::capacity(&instances),
+        Some(Commands::Migration) => reports::replacement_pairs(&instances),
+        Some(Commands::FuzzGaps { fuzz_dir }) => {
+            reports::fuzz_gaps(&instances, &root.join(fuzz_dir));
+        },
+        Some(Commands::I18n) => {
+            reports::list_marker(&instances, "requires_locale", "locale-sensitive items");
+        },
+        Some(Commands::ErrorHandling) => reports::error_handling(&instances),
+        Some(Commands::Numerics) => {
+            reports::list_marker(&instances, "approximation", "numerical approximations");
+        },
+        Some(Commands::Concurrency) => reports::concurrency(&instances),
+        Some(Commands::Authz) => reports::authz(&instances),
+        Some(Commands::CompatTests { tests_dir }) => {
+            reports::compat_test_gaps(&instances, &root.join(tests_dir));
+        },
+        Some(Commands::ReleaseReadiness { penalty, min_score }) => {
+            if !release::report(&instances, penalty, *min_score) {
+                process::exit(1);
+            }
+        },
+        Some(Commands::Secrets) => reports::secrets(&instances),
+        Some(Commands::Owners) => reports::owners(&instances),
+        Some(Commands::ReviewGate { today }) => {
+            if !reports::review_gate(&instances, today.unwrap_or_else(Date::today)) {
+                process::exit(1);
+            }
+        },
+        Some(Commands::Precision) => reports::precision(&instances),
+        Some(Commands::Count { macros, severity }) => {
+            println!("{}", count(&instances, macros, *severity, severities));
+        },
+        Some(Commands::Shutdown) => reports::list_marker(
+            &instances,
+            "requires_graceful_shutdown",
+            "components needing graceful shutdown",
+        ),
+        Some(Commands::Resilience) => reports::resilience(&instances),
+        Some(Commands::LoadTestGate) => reports::load_test_gate(&instances),
+        Some(Commands::A11y) => reports::list_marker(
+            &instances,
+            "requires_accessibility_review",
+            "accessibility review items",
+        ),
+        Some(Commands::Chaos) => reports::chaos(&instances),
+        Some(Commands::DocsReview) => reports::list_marker(
+            &instances,
+            "requires_documentation_review",
+            "documentation items needing review",
+        ),
+        Some(Commands::Observability) => reports::observability(&instances),
+        Some(Commands::InjectionSurface) => reports::injection_surface(&instances),
+        Some(Commands::ReleaseNotesGaps { changelog }) => {
+            reports::release_notes_gaps(&instances, &root.join(changelog));
+        },
+        Some(Commands::PropertyTests { tests_dir }) => {
+            reports::property_test_gaps(&instances, &root.join(tests_dir));
+        },
+        Some(Commands::Stale {
+            current_version,
+            today,
+        }) => {
+            if !stale::report(
+                &instances,
+                today.unwrap_or_else(Date::today),
+                *current_version,
+            ) {
+                process::exit(1);
+            }
+        },
+        Some(Commands::Baseline { output }) => match baseline::write(&instances, output) {
+            Ok(()) => println!(
+                "{}",
+                format!(
+                    "Recorded {} findings in {}",
+                    instances.len(),
+                    output.display()
+                )
+                .green()
+            ),
+            Err(err) => {
+                eprintln!("Failed to write baseline {}", err);
+                process::exit(1);
+            },
+        },
+        Some(Commands::Diff { .. }) => unreachable!("diff is handled before the reports"),
+        Some(Commands::BreakingChanges { .. }) => {
+            unreachable!("breaking-changes is handled before the reports")
+        },
+        Some(Commands::Watch { .. }) => unreachable!("watch is handled before the reports"),
+        Some(Commands::Coverage { lcov }) => {
+            let lcov = root.join(lcov);
+            let report = coverage::Lcov::load(&lcov).unwrap_or_else(|err| {
+                eprintln!("Failed to read coverage report {}", err);
+                process::exit(2);
+            });
+            coverage::report(&instances, root, &report, &lcov);
+        },
+        Some(Commands::Validate) => {
+            if !validate::report(&instances, root) {
+                process::exit(1);
+            }
+        },
+        None => {
+            // Default to list if no subcommand provided
+            let (shown, hidden) = hide_info(&instances, false, severities);
+            list_macros(&shown, false, hyperlinks);
+            print_hidden_footer(hidden, "list --all");
+        },
     }
+
+    check_thresholds(&cli, &instances);
 }
 
-/// Scan a directory for code status macros
-fn scan_directory(
-    path: &Path,
-    include_pattern: &Option<Regex>,
-    exclude_pattern: &Option<Regex>,
-    macro_regexes: &[(String, Regex)],
-    max_depth: Option<usize>,
-    skip_default_dirs: bool,
-) -> Vec<MacroInstance> {
-    let mut instances = Vec::new();
-    let max_depth = max_depth.unwrap_or(usize::MAX);
-
-    // First collect all eligible files to avoid recursive regex checks
-    let walker = WalkDir::new(path)
-        .follow_links(true)
-        .max_depth(max_depth)
+/// The directory findings are reported relative to: the scan path, or its parent for a single file
+fn scan_root(path: &Path) -> &Path {
+    if path.is_file() {
+        path.parent().unwrap_or(path)
+    } else {
+        path
+    }
+}
+
+/// Exit when an option names a macro that is neither built in nor registered as extra
+fn check_macro_names(cli: &Cli, known_macros: &[String]) {
+    let mut named: Vec<&str> = cli.only.iter().map(String::as_str).collect();
+    named.extend(cli.fail_on.iter().map(String::as_str));
+    named.extend(cli.sarif_level.iter().map(|(name, _)| name.as_str()));
+    named.extend(cli.junit_fail_on.iter().map(String::as_str));
+    named.extend(
+        cli.exclude_macro_in_path
+            .iter()
+            .map(|rule| rule.macro_name.as_str()),
+    );
+    match &cli.command {
+        Some(Commands::Count { macros, .. }) => named.extend(macros.iter().map(String::as_str)),
+        Some(Commands::ReleaseReadiness { penalty, .. }) => {
+            named.extend(penalty.iter().map(release::Penalty::macro_name));
+        },
+        _ => {},
+    }
+
+    if let Some(unknown) = named
         .into_iter()
-        .filter_map(Result::ok);
+        .find(|name| !known_macros.iter().any(|k| k == name))
+    {
+        eprintln!(
+            "Unknown macro `{}`; register custom markers with --extra-macros",
+            unknown
+        );
+        process::exit(2);
+    }
+}
+
+/// Make finding paths relative to `root`, or absolute with `absolute`
+///
+/// Reports that read a finding's file again join its path onto `root`.
+fn show_paths(instances: &mut [MacroInstance], root: &Path, absolute: bool) {
+    let absolute_root = absolute_root(root, absolute);
+    for instance in instances {
+        if let Some(shown) = shown_path(&instance.path, root, absolute_root.as_deref()) {
+            instance.path = shown;
+        }
+    }
+}
 
-    let files: Vec<PathBuf> = walker
-        .filter(|entry| {
-            let path = entry.path();
+/// The canonical scan root with `--absolute`
+fn absolute_root(root: &Path, absolute: bool) -> Option<PathBuf> {
+    if absolute {
+        fs::canonicalize(root).ok()
+    } else {
+        None
+    }
+}
 
-            // Skip if not a file
-            if !path.is_file() {
-                return false;
-            }
+/// `path` relative to `root`, or under `absolute_root` when given; `None` outside `root`
+fn shown_path(path: &Path, root: &Path, absolute_root: Option<&Path>) -> Option<PathBuf> {
+    let relative = path.strip_prefix(root).ok()?;
+    Some(match absolute_root {
+        Some(absolute_root) => absolute_root.join(relative),
+        None => relative.to_path_buf(),
+    })
+}
 
-            // Skip if not a Rust file
-            if !path.to_string_lossy().ends_with(".rs") {
-                return false;
-            }
+/// Order findings by `key`, breaking ties by path and line
+fn sort_instances(
+    instances: &mut [MacroInstance],
+    key: SortKey,
+    reverse: bool,
+    severities: &Severities,
+) {
+    let position =
+        |a: &MacroInstance, b: &MacroInstance| a.path.cmp(&b.path).then(a.line.cmp(&b.line));
+    instances.sort_by(|a, b| {
+        let order = match key {
+            SortKey::Path => Ordering::Equal,
+            SortKey::Line => a.line.cmp(&b.line),
+            SortKey::Macro => a.macro_name.cmp(&b.macro_name),
+            SortKey::Severity => severities
+                .of(&a.macro_name)
+                .cmp(&severities.of(&b.macro_name)),
+        };
+        let order = order.then_with(|| position(a, b));
+        if reverse {
+            order.reverse()
+        } else {
+            order
+        }
+    });
+}
 
-            // Skip default excluded directories if enabled
-            if skip_default_dirs {
-                let path_str = path.to_string_lossy();
-                if DEFAULT_EXCLUDE_DIRS
-                    .iter()
-                    .any(|&dir| path_str.contains(dir))
-                {
-                    return false;
-                }
-            }
+/// Apply `--exclude-macro-in-path`, `--open-only` and `--min-severity` to freshly scanned findings
+fn drop_excluded(
+    cli: &Cli,
+    root: &Path,
+    severities: &Severities,
+    instances: &mut Vec<MacroInstance>,
+) {
+    // Drop macros excluded under specific paths, keeping everything else in those files
+    if !cli.exclude_macro_in_path.is_empty() {
+        instances.retain(|instance| {
+            let relative_path = instance.path.strip_prefix(root).unwrap_or(&instance.path);
+            !cli.exclude_macro_in_path
+                .iter()
+                .any(|rule| rule.matches(instance, relative_path))
+        });
+    }
 
-            // Apply include/exclude patterns
-            let path_str = path.to_string_lossy();
-            if let Some(pattern) = include_pattern {
-                if !pattern.is_match(&path_str) {
-                    return false;
-                }
-            }
+    if cli.open_only {
+        // Reviews are checked against the concern's commit date when blame is asked for
+        if cli.blame {
+            blame::annotate(instances);
+        }
+        review::retain_open(instances);
+    }
 
-            if let Some(pattern) = exclude_pattern {
-                if pattern.is_match(&path_str) {
-                    return false;
-                }
-            }
+    if let Some(min_severity) = cli.min_severity {
+        instances.retain(|instance| severities.of(&instance.macro_name) <= min_severity);
+    }
+}
 
-            true
-        })
-        .map(|entry| entry.path().to_path_buf())
+/// Split off Info findings unless `all` is set, returning the rest and how many were hidden
+fn hide_info(
+    instances: &[MacroInstance],
+    all: bool,
+    severities: &Severities,
+) -> (Vec<MacroInstance>, usize) {
+    let shown: Vec<MacroInstance> = instances
+        .iter()
+        .filter(|i| all || severities.of(&i.macro_name) != Severity::Info)
+        .cloned()
         .collect();
+    let hidden = instances.len() - shown.len();
+    (shown, hidden)
+}
 
-    // Process each file
-    for path in &files {
-        if let Ok(content) = fs::read_to_string(path) {
-            let mut file_instances = scan_file(path, &content, macro_regexes);
-            instances.append(&mut file_instances);
-        }
+/// Tell the user how many Info findings `list` left out and how to show them
+fn print_hidden_footer(hidden: usize, flag: &str) {
+    if let Some(footer) = hidden_footer(hidden, flag) {
+        println!("{}", footer.dimmed());
     }
+}
 
+/// The footer text for `hidden` Info findings, when there are any
+fn hidden_footer(hidden: usize, flag: &str) -> Option<String> {
+    (hidden > 0).then(|| format!("{} info findings hidden; use {} to show", hidden, flag))
+}
+
+/// Count findings of the given macros (all when empty) and severity (any when `None`)
+fn count(
+    instances: &[MacroInstance],
+    macros: &[String],
+    severity: Option<Severity>,
+    severities: &Severities,
+) -> usize {
     instances
+        .iter()
+        .filter(|i| macros.is_empty() || macros.contains(&i.macro_name))
+        .filter(|i| severity.is_none_or(|s| severities.of(&i.macro_name) == s))
+        .count()
 }
 
-/// Scan a single file for code status macros
-fn scan_file(path: &Path, content: &str, macro_regexes: &[(String, Regex)]) -> Vec<MacroInstance> {
-    let mut instances = Vec::new();
-    let lines: Vec<&str> = content.lines().collect();
-
-    for (line_idx, line) in lines.iter().enumerate() {
-        // Check for macros in the current line
-        for (macro_name, regex) in macro_regexes {
-            if let Some(caps) = regex.captures(line) {
-                let argument = caps.get(1).map(|m| m.as_str().trim().to_string());
-
-                // Get context (next line after the macro)
-                let context = if line_idx + 1 < lines.len() {
-                    lines[line_idx + 1].trim().to_string()
-                } else {
-                    String::new()
-                };
-
-                instances.push(MacroInstance {
-                    path: path.to_path_buf(),
-                    line: line_idx + 1,
-                    macro_name: macro_name.clone(),
-                    argument,
-                    context,
-                });
+/// Exit with code 1 when `--fail-over` or `--fail-on` is violated
+///
+/// Runs after the report has been printed. With `search`, `--fail-over` only
+/// counts the searched macros. Reasons go to stderr so JSON output stays valid.
+fn check_thresholds(cli: &Cli, instances: &[MacroInstance]) {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for instance in instances {
+        *counts.entry(instance.macro_name.clone()).or_default() += 1;
+    }
+    check_counts(cli, &counts);
+}
+
+/// Exit with code 1 when per-macro finding counts break `--fail-over` or `--fail-on`
+fn check_counts(cli: &Cli, counts: &HashMap<String, usize>) {
+    let mut failed = false;
+
+    if let Some(limit) = cli.fail_over {
+        let searched: Option<Vec<&str>> = match &cli.command {
+            Some(Commands::Search { macros }) => Some(macros.split(',').map(str::trim).collect()),
+            _ => None,
+        };
+        let mut by_macro: Vec<(&str, usize)> = counts
+            .iter()
+            .filter(|(name, _)| {
+                searched
+                    .as_ref()
+                    .is_none_or(|names| names.contains(&name.as_str()))
+            })
+            .map(|(name, &count)| (name.as_str(), count))
+            .collect();
+        let counted: usize = by_macro.iter().map(|(_, count)| count).sum();
+
+        if counted > limit {
+            failed = true;
+            by_macro.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+
+            eprintln!(
+                "{}",
+                format!(
+                    "Found {} findings, over the --fail-over limit of {}:",
+                    counted, limit
+                )
+                .red()
+                .bold()
+            );
+            for (macro_name, count) in by_macro {
+                eprintln!("  {:25} : {}", macro_name, count);
             }
         }
     }
 
-    instances
+    for macro_name in &cli.fail_on {
+        let count = counts.get(macro_name).copied().unwrap_or(0);
+        if count > 0 {
+            failed = true;
+            eprintln!(
+                "{}",
+                format!("Found {} #[{}] findings (--fail-on)", count, macro_name)
+                    .red()
+                    .bold()
+            );
+        }
+    }
+
+    if failed {
+        process::exit(1);
+    }
 }
 
 /// List all macros found in the codebase
-fn list_macros(instances: &[MacroInstance]) {
+///
+/// With `with_explanations`, each macro type is explained after its first finding.
+/// With `hyperlinks`, locations link to their files.
+fn list_macros(instances: &[MacroInstance], with_explanations: bool, hyperlinks: Option<&Path>) {
     println!(
         "{}",
         format!("Found {} code status macro instances:", instances.len()).green()
     );
     println!();
 
+    let mut explained = HashSet::new();
     for instance in instances {
-        let rel_path = instance.path.display();
-        let line_info = format!("{}:{}", rel_path, instance.line);
-        let arg_display = match &instance.argument {
+        print_instance(instance, hyperlinks);
+        if with_explanations {
+            print_explanation_once(instance, &mut explained);
+        }
+        println!();
+    }
+}
+
+/// List all macros grouped under severity headers, most severe first
+fn list_by_severity(
+    instances: &[MacroInstance],
+    with_explanations: bool,
+    hyperlinks: Option<&Path>,
+    severities: &Severities,
+) {
+    println!(
+        "{}",
+        format!("Found {} code status macro instances:", instances.len()).green()
+    );
+    println!();
+
+    let mut explained = HashSet::new();
+    for (severity, matching) in severity_sections(instances, severities) {
+        println!(
+            "{}",
+            severity::colored(
+                severity,
+                &format!("== {} ({}) ==", severity, matching.len())
+            )
+        );
+        println!();
+        for instance in matching {
+            print_instance(instance, hyperlinks);
+            if with_explanations {
+                print_explanation_once(instance, &mut explained);
+            }
+            println!();
+        }
+    }
+}
+
+/// Findings split by severity, most severe first, leaving out empty severities
+fn severity_sections<'a>(
+    instances: &'a [MacroInstance],
+    severities: &Severities,
+) -> Vec<(Severity, Vec<&'a MacroInstance>)> {
+    Severity::ALL
+        .into_iter()
+        .map(|severity| {
+            let matching: Vec<_> = instances
+                .iter()
+                .filter(|i| severities.of(&i.macro_name) == severity)
+                .collect();
+            (severity, matching)
+        })
+        .filter(|(_, matching)| !matching.is_empty())
+        .collect()
+}
+
+/// List findings grouped by identical macro and argument, in order of first appearance
+///
+/// Each group gets one header with its count, followed by its locations.
+fn list_collapsed(instances: &[MacroInstance], with_explanations: bool, hyperlinks: Option<&Path>) {
+    let groups = collapse(instances);
+
+    println!(
+        "{}",
+        format!(
+            "Found {} code status macro instances in {} groups:",
+            instances.len(),
+            groups.len()
+        )
+        .green()
+    );
+    println!();
+
+    let mut explained = HashSet::new();
+    for (first, members) in &groups {
+        let arg_display = match &first.argument {
             Some(arg) if !arg.is_empty() => format!("({})", arg.yellow()),
             _ => "".to_string(),
         };
-
         println!(
-            "{} {}{}",
-            line_info.blue(),
-            format!("#[{}]", instance.macro_name).green(),
-            arg_display
+            "{}{} {}",
+            format!("#[{}]", first.macro_name).green(),
+            arg_display,
+            format!("x{}", members.len()).cyan().bold()
         );
-        println!("    {}", instance.context.trim());
+        if with_explanations {
+            print_explanation_once(first, &mut explained);
+        }
+        for member in members {
+            println!("    {}", location(member, hyperlinks));
+        }
         println!();
     }
 }
 
+/// Findings grouped by identical macro and argument, each with its first finding
+fn collapse(instances: &[MacroInstance]) -> Vec<(&MacroInstance, Vec<&MacroInstance>)> {
+    let mut groups: Vec<(&MacroInstance, Vec<&MacroInstance>)> = Vec::new();
+    for instance in instances {
+        let existing = groups.iter_mut().find(|(first, _)| {
+            first.macro_name == instance.macro_name && first.argument == instance.argument
+        });
+        match existing {
+            Some((_, members)) => members.push(instance),
+            None => groups.push((instance, vec![instance])),
+        }
+    }
+    groups
+}
+
+/// Print the explanation for an instance's macro unless it was already shown
+fn print_explanation_once<'a>(instance: &'a MacroInstance, explained: &mut HashSet<&'a str>) {
+    if let Some(text) = explanation_once(instance, explained) {
+        println!("    {}", text.dimmed());
+    }
+}
+
+/// The explanation line for an instance's macro, the first time that macro is seen
+fn explanation_once<'a>(
+    instance: &'a MacroInstance,
+    explained: &mut HashSet<&'a str>,
+) -> Option<String> {
+    if !explained.insert(instance.macro_name.as_str()) {
+        return None;
+    }
+    explain::explanation(&instance.macro_name)
+        .map(|text| format!("#[{}]: {}", instance.macro_name, text))
+}
+
+/// A finding's `file:line`, as a terminal hyperlink when `hyperlinks` gives
+/// the directory finding paths are relative to
+fn location(instance: &MacroInstance, hyperlinks: Option<&Path>) -> String {
+    let line_info = format!("{}:{}", instance.path.display(), instance.line)
+        .blue()
+        .to_string();
+    match hyperlinks {
+        Some(root) => hyperlink::wrap(&line_info, &root.join(&instance.path), instance.line),
+        None => line_info,
+    }
+}
+
+/// Print a single finding with its location, short ID, argument and context
+fn print_instance(instance: &MacroInstance, hyperlinks: Option<&Path>) {
+    let line_info = location(instance, hyperlinks);
+    let arg_display = match &instance.argument {
+        Some(arg) if !arg.is_empty() => format!("({})", arg.yellow()),
+        _ => "".to_string(),
+    };
+    let item_display = match (instance.item_kind, &instance.item_name) {
+        (Some(kind), Some(name)) if !name.starts_with("impl") => format!(" on {} {}", kind, name),
+        (_, Some(name)) => format!(" on {}", name),
+        _ => String::new(),
+    };
+    let blame_display = instance.blame.as_ref().map_or_else(String::new, |b| {
+        format!(" ({}, {})", b.author, b.commit_date)
+    });
+
+    println!(
+        "{} {} {}{}{}{}",
+        line_info,
+        format!("[{}]", instance.short_id()).dimmed(),
+        format!("#[{}]", instance.macro_name).green(),
+        arg_display,
+        item_display.dimmed(),
+        blame_display.dimmed()
+    );
+    println!("    {}", instance.context_block("    "));
+}
+
 /// Generate a summary report of macro usage
-fn generate_summary(instances: &[MacroInstance]) {
-    let mut count_by_macro = HashMap::new();
-    let mut count_by_file = HashMap::new();
+///
+/// Macro types are listed by count, most used first, or by name with `--sort macro`.
+fn generate_summary(
+    instances: &[MacroInstance],
+    severities: &Severities,
+    sort: SortKey,
+    reverse: bool,
+) {
+    let mut count_by_macro = BTreeMap::new();
+    let mut count_by_file = BTreeMap::new();
+    let mut count_by_severity = BTreeMap::new();
 
     for instance in instances {
         *count_by_macro
             .entry(instance.macro_name.clone())
             .or_insert(0) += 1;
         *count_by_file.entry(instance.path.clone()).or_insert(0) += 1;
+        *count_by_severity
+            .entry(severities.of(&instance.macro_name))
+            .or_insert(0) += 1;
     }
 
     println!("{}", "== Macro Usage Summary ==".green().bold());
@@ -310,15 +1428,51 @@ fn generate_summary(instances: &[MacroInstance]) {
     );
     println!();
 
+    println!("{}", "By severity:".yellow());
+    for severity in Severity::ALL {
+        let count = count_by_severity.get(&severity).copied().unwrap_or(0);
+        println!(
+            "  {} : {}",
+            severity::colored(severity, &format!("{:25}", severity)),
+            count
+        );
+    }
+    println!();
+
     println!("{}", "By macro type:".yellow());
-    for (macro_name, count) in count_by_macro.iter() {
+    let mut by_macro: Vec<_> = count_by_macro.iter().collect();
+    by_macro.sort_by(|a, b| {
+        let order = match sort {
+            SortKey::Macro => a.0.cmp(b.0),
+            _ => b.1.cmp(a.1).then(a.0.cmp(b.0)),
+        };
+        if reverse {
+            order.reverse()
+        } else {
+            order
+        }
+    });
+    for (macro_name, count) in by_macro {
         println!("  {:25} : {}", macro_name, count);
     }
     println!();
 
+    let tests = reports::test_burndown(instances);
+    if tests.items > 0 {
+        println!(
+            "{}",
+            format!(
+                "Tests needed: {} missing across {} items ({} of {} written)",
+                tests.missing, tests.items, tests.have, tests.want
+            )
+            .yellow()
+        );
+        println!();
+    }
+
     println!("{}", "Top 5 files by macro usage:".yellow());
     let mut files: Vec<_> = count_by_file.iter().collect();
-    files.sort_by(|a, b| b.1.cmp(a.1));
+    files.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
 
     for (file, count) in files.iter().take(5) {
         println!("  {:50} : {}", file.display(), count);
@@ -361,7 +1515,285 @@ fn search_macros(instances: &[MacroInstance], macros_str: &str) {
             format!("#[{}]", instance.macro_name).green(),
             arg_display
         );
-        println!("    {}", instance.context.trim());
+        println!("    {}", instance.context_block("    "));
         println!();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Findings in `file` under the scan root `root`, filtered by `args`
+    fn scan_filtered(args: &[&str], root: &Path, file: &Path, source: &str) -> Vec<MacroInstance> {
+        let cli =
+            Cli::parse_from(std::iter::once("code-status-scanner").chain(args.iter().copied()));
+        let id_path = file.strip_prefix(root).unwrap_or(file);
+        let mut instances = Scanner::new().scan_source(file, id_path, source);
+        drop_excluded(&cli, root, &Severities::default(), &mut instances);
+        instances
+    }
+
+    fn scan(source: &str) -> Vec<MacroInstance> {
+        let file = Path::new("src/lib.rs");
+        Scanner::new().scan_source(file, file, source)
+    }
+
+    #[test]
+    fn parses_macro_path_rules() {
+        let rule = parse_macro_path_rule("untested:./examples/").unwrap();
+        assert_eq!(rule.macro_name, "untested");
+        assert_eq!(rule.path_prefix, "examples/");
+
+        assert!(parse_macro_path_rule("untested").is_err());
+        assert!(parse_macro_path_rule("untested:").is_err());
+        assert!(parse_macro_path_rule("not a macro:examples/").is_err());
+    }
+
+    #[test]
+    fn exclude_macro_in_path_applies_to_a_single_file_scan() {
+        let file = Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/src/main.rs"));
+        let root = scan_root(file);
+        assert_eq!(root, file.parent().unwrap());
+
+        let source = "#[untested]\n#[security_sensitive]\nfn handler() {}\n";
+        let args = ["--exclude-macro-in-path", "untested:main.rs"];
+        let found = scan_filtered(&args, root, file, source);
+        let names: Vec<_> = found.iter().map(|i| i.macro_name.as_str()).collect();
+        assert_eq!(names, ["security_sensitive"]);
+    }
+
+    #[test]
+    fn exclude_macro_in_path_drops_only_that_macro_under_the_prefix() {
+        let source = "#[untested]\n#[security_sensitive]\nfn handler() {}\n";
+        let args = ["--exclude-macro-in-path", "untested:examples/"];
+        let root = Path::new("project");
+
+        let in_examples = scan_filtered(&args, root, &root.join("examples/demo.rs"), source);
+        let names: Vec<_> = in_examples.iter().map(|i| i.macro_name.as_str()).collect();
+        assert_eq!(names, ["security_sensitive"]);
+
+        let in_src = scan_filtered(&args, root, &root.join("src/lib.rs"), source);
+        let names: Vec<_> = in_src.iter().map(|i| i.macro_name.as_str()).collect();
+        assert_eq!(names, ["untested", "security_sensitive"]);
+    }
+
+    #[test]
+    fn severity_sections_run_from_critical_to_info() {
+        let instances = scan(
+            r#"
+#[needs("docs")]
+fn helper() {}
+
+#[owner("platform")]
+#[security_sensitive]
+#[untested]
+fn login() {}
+
+#[panic_path]
+fn unwrap_config() {}
+"#,
+        );
+
+        let sections: Vec<_> = severity_sections(&instances, &Severities::default())
+            .into_iter()
+            .map(|(severity, found)| (severity, found.len()))
+            .collect();
+        assert_eq!(
+            sections,
+            [
+                (Severity::Critical, 2),
+                (Severity::Warning, 2),
+                (Severity::Info, 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn severity_sections_skip_empty_severities() {
+        let instances = scan("#[untested]\nfn parse() {}\n#[needs(\"docs\")]\nfn helper() {}\n");
+
+        let sections: Vec<_> = severity_sections(&instances, &Severities::default())
+            .into_iter()
+            .map(|(severity, found)| (severity, found.len()))
+            .collect();
+        assert_eq!(sections, [(Severity::Warning, 2)]);
+    }
+
+    #[test]
+    fn each_macro_type_is_explained_once() {
+        let instances = scan(
+            r#"
+#[untested]
+fn parse() {}
+
+#[untested]
+#[needs("docs")]
+fn render() {}
+
+#[needs("error handling")]
+#[untested]
+fn save() {}
+"#,
+        );
+
+        let mut explained = HashSet::new();
+        let shown: Vec<_> = instances
+            .iter()
+            .filter_map(|instance| explanation_once(instance, &mut explained))
+            .collect();
+        assert_eq!(
+            shown,
+            [
+                "#[untested]: Marks functions that haven't been properly tested",
+                "#[needs]: Indicates a specific need (e.g., refactoring, optimization)"
+            ]
+        );
+    }
+
+    #[test]
+    fn every_macro_has_an_explanation() {
+        for name in code_status_core::MACRO_NAMES {
+            assert!(
+                explain::explanation(name).is_some(),
+                "{} is unexplained",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn count_applies_macro_and_severity_filters() {
+        let instances = scan(
+            r#"
+#[untested]
+#[security_sensitive]
+fn login() {}
+
+#[untested]
+fn parse() {}
+
+#[needs("docs")]
+#[owner("platform")]
+fn helper() {}
+"#,
+        );
+        let severities = Severities::default();
+        let untested = ["untested".to_string()];
+        let mixed = ["untested".to_string(), "owner".to_string()];
+
+        assert_eq!(count(&instances, &[], None, &severities), 5);
+        assert_eq!(count(&instances, &untested, None, &severities), 2);
+        assert_eq!(
+            count(&instances, &[], Some(Severity::Warning), &severities),
+            3
+        );
+        assert_eq!(
+            count(&instances, &mixed, Some(Severity::Info), &severities),
+            1
+        );
+        assert_eq!(
+            count(&instances, &untested, Some(Severity::Critical), &severities),
+            0
+        );
+    }
+
+    #[test]
+    fn locations_are_hyperlinked_only_when_enabled() {
+        let instances = scan("#[untested]\nfn parse() {}\n");
+        let root = Path::new("/nonexistent/project");
+
+        let plain = location(&instances[0], None);
+        assert!(!plain.contains("\x1b]8;;"), "{:?}", plain);
+        assert!(plain.contains("src/lib.rs:1"), "{:?}", plain);
+
+        let linked = location(&instances[0], Some(root));
+        assert!(
+            linked.starts_with("\x1b]8;;file:///nonexistent/project/src/lib.rs#1\x1b\\"),
+            "{:?}",
+            linked
+        );
+        assert!(linked.ends_with("\x1b]8;;\x1b\\"), "{:?}", linked);
+        assert!(linked.contains(&plain), "{:?}", linked);
+    }
+
+    #[test]
+    fn identical_findings_collapse_into_one_group() {
+        let instances = scan(
+            r#"
+#[needs("error handling")]
+fn parse() {}
+
+#[untested]
+fn render() {}
+
+#[needs("error handling")]
+fn save() {}
+
+#[needs("docs")]
+fn load() {}
+
+#[needs("error handling")]
+fn close() {}
+"#,
+        );
+
+        let groups: Vec<_> = collapse(&instances)
+            .into_iter()
+            .map(|(first, members)| {
+                let lines: Vec<_> = members.iter().map(|m| m.line).collect();
+                (
+                    first.macro_name.as_str(),
+                    first.argument_value().unwrap_or_default(),
+                    members.len(),
+                    lines,
+                )
+            })
+            .collect();
+        assert_eq!(
+            groups,
+            [
+                ("needs", "error handling", 3, vec![2, 8, 14]),
+                ("untested", "", 1, vec![5]),
+                ("needs", "docs", 1, vec![11])
+            ]
+        );
+    }
+
+    const MIXED_SEVERITIES: &str = r#"
+#[security_sensitive]
+#[owner("platform")]
+fn login() {}
+
+#[untested]
+#[assumptions("input is ASCII")]
+fn parse() {}
+
+#[owner("web")]
+fn render() {}
+"#;
+
+    #[test]
+    fn info_findings_are_hidden_by_default() {
+        let instances = scan(MIXED_SEVERITIES);
+
+        let (shown, hidden) = hide_info(&instances, false, &Severities::default());
+        let names: Vec<_> = shown.iter().map(|i| i.macro_name.as_str()).collect();
+        assert_eq!(names, ["security_sensitive", "untested"]);
+        assert_eq!(hidden, 3);
+        assert_eq!(
+            hidden_footer(hidden, "--all").as_deref(),
+            Some("3 info findings hidden; use --all to show")
+        );
+    }
+
+    #[test]
+    fn all_shows_info_findings_without_a_footer() {
+        let instances = scan(MIXED_SEVERITIES);
+
+        let (shown, hidden) = hide_info(&instances, true, &Severities::default());
+        assert_eq!(shown.len(), instances.len());
+        assert_eq!(hidden, 0);
+        assert_eq!(hidden_footer(hidden, "--all"), None);
+    }
+}
@@ -0,0 +1,161 @@
+//! Markdown output for `--format markdown`, to paste into pull request comments and wikis.
+//!
+//! Paths are relative to the scan path (unless `--absolute` is given), so
+//! `file:line` links resolve when the document is read from there.
+
+use std::collections::{BTreeMap, HashMap};
+
+use crate::{reports, severity::Severities, MacroInstance, Severity};
+
+/// How many files `summary` ranks
+const TOP_FILES: usize = 5;
+
+/// Print findings as count tables followed by a collapsible list per macro
+pub fn print_findings(instances: &[&MacroInstance]) {
+    let files = count_by(instances, display_path);
+
+    println!("## Code status report");
+    println!();
+    println!(
+        "**{}** findings in **{}** files.",
+        instances.len(),
+        files.len()
+    );
+    println!();
+    print_counts(
+        "Macro",
+        &count_by(instances, |i| format!("`#[{}]`", i.macro_name)),
+    );
+    print_counts("File", &files);
+
+    let mut by_macro: BTreeMap<&str, Vec<&MacroInstance>> = BTreeMap::new();
+    for instance in instances {
+        by_macro
+            .entry(&instance.macro_name)
+            .or_default()
+            .push(instance);
+    }
+    for (macro_name, members) in by_macro {
+        println!("<details>");
+        println!(
+            "<summary><code>#[{}]</code> ({})</summary>",
+            macro_name,
+            members.len()
+        );
+        println!();
+        for instance in members {
+            let item = instance
+                .item_name
+                .as_deref()
+                .map_or_else(String::new, |item| format!(" on `{}`", item));
+            let argument = instance
+                .argument_value()
+                .filter(|arg| !arg.is_empty())
+                .map_or_else(String::new, |arg| format!(": {}", arg));
+            println!("- {}{}{}", link(instance), item, argument);
+        }
+        println!();
+        println!("</details>");
+        println!();
+    }
+}
+
+/// Print counts per severity and macro, then the files with the most findings as a ranked list
+pub fn print_summary(instances: &[MacroInstance], severities: &Severities) {
+    let tests = reports::test_burndown(instances);
+    let instances: Vec<&MacroInstance> = instances.iter().collect();
+
+    println!("## Code status summary");
+    println!();
+    println!("**{}** findings.", instances.len());
+    println!();
+
+    // Every tier, most severe first, including empty ones
+    let by_severity: Vec<(String, usize)> = Severity::ALL
+        .into_iter()
+        .map(|severity| {
+            let count = instances
+                .iter()
+                .filter(|i| severities.of(&i.macro_name) == severity)
+                .count();
+            (severity.to_string(), count)
+        })
+        .collect();
+    print_counts("Severity", &by_severity);
+    print_counts(
+        "Macro",
+        &count_by(&instances, |i| format!("`#[{}]`", i.macro_name)),
+    );
+
+    if tests.items > 0 {
+        println!(
+            "**Tests needed:** {} missing across {} items ({} of {} written).",
+            tests.missing, tests.items, tests.have, tests.want
+        );
+        println!();
+    }
+
+    println!("### Top {} files", TOP_FILES);
+    println!();
+    for (rank, (file, count)) in count_by(&instances, display_path)
+        .iter()
+        .take(TOP_FILES)
+        .enumerate()
+    {
+        println!("{}. [{}]({}) — {}", rank + 1, file, uri(file), count);
+    }
+}
+
+/// Count findings by `key`, most common first and then by key
+fn count_by(
+    instances: &[&MacroInstance],
+    key: impl Fn(&MacroInstance) -> String,
+) -> Vec<(String, usize)> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for instance in instances {
+        *counts.entry(key(instance)).or_default() += 1;
+    }
+    let mut counts: Vec<_> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts
+}
+
+/// Print a two-column table of counts under a heading named after `label`
+fn print_counts(label: &str, counts: &[(String, usize)]) {
+    println!("### By {}", label.to_lowercase());
+    println!();
+    println!("| {} | Count |", label);
+    println!("|---|---:|");
+    for (key, count) in counts {
+        println!("| {} | {} |", key.replace('|', "\\|"), count);
+    }
+    println!();
+}
+
+/// A finding's `file:line` as a link to the line
+fn link(instance: &MacroInstance) -> String {
+    let path = display_path(instance);
+    format!(
+        "[{}:{}]({}#L{})",
+        path,
+        instance.line,
+        uri(&path),
+        instance.line
+    )
+}
+
+/// A finding's path with forward slashes
+fn display_path(instance: &MacroInstance) -> String {
+    instance
+        .path
+        .display()
+        .to_string()
+        .replace('\\', "/")
+        .trim_start_matches("./")
+        .to_string()
+}
+
+/// A relative path as a link target
+fn uri(path: &str) -> String {
+    path.replace(' ', "%20")
+}
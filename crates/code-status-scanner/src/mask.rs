@@ -0,0 +1,205 @@
+//! Blanking of comments and literals, so markers quoted in them are not reported.
+
+/// Replace comments and string and character literals with spaces
+///
+/// Byte offsets and line breaks are preserved, so a position in the result is
+/// the same position in `source`. Lifetimes are left alone.
+pub fn code_only(source: &str) -> String {
+    let bytes = source.as_bytes();
+    let mut out = bytes.to_vec();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let end = match bytes[i] {
+            b'/' if bytes.get(i + 1) == Some(&b'/') => line_comment_end(bytes, i),
+            b'/' if bytes.get(i + 1) == Some(&b'*') => block_comment_end(bytes, i),
+            b'"' => string_end(bytes, i),
+            b'r' if starts_raw_string(bytes, i) => raw_string_end(bytes, i),
+            b'\'' => match char_literal_end(source, i) {
+                Some(end) => end,
+                None => {
+                    i += 1;
+                    continue;
+                },
+            },
+            _ => {
+                i += 1;
+                continue;
+            },
+        };
+
+        for byte in &mut out[i..end] {
+            if *byte != b'\n' && *byte != b'\r' {
+                *byte = b' ';
+            }
+        }
+        i = end;
+    }
+
+    String::from_utf8(out).expect("blanking whole literals keeps the text valid UTF-8")
+}
+
+fn is_ident_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_'
+}
+
+fn line_comment_end(bytes: &[u8], start: usize) -> usize {
+    bytes[start..]
+        .iter()
+        .position(|&b| b == b'\n')
+        .map_or(bytes.len(), |offset| start + offset)
+}
+
+/// The end of a block comment, which may nest
+fn block_comment_end(bytes: &[u8], start: usize) -> usize {
+    let mut depth = 0usize;
+    let mut i = start;
+    while i + 1 < bytes.len() {
+        match (bytes[i], bytes[i + 1]) {
+            (b'/', b'*') => {
+                depth += 1;
+                i += 2;
+            },
+            (b'*', b'/') => {
+                depth -= 1;
+                i += 2;
+                if depth == 0 {
+                    return i;
+                }
+            },
+            _ => i += 1,
+        }
+    }
+    bytes.len()
+}
+
+/// The end of a `"..."` literal, honoring backslash escapes
+fn string_end(bytes: &[u8], start: usize) -> usize {
+    let mut i = start + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'"' => return i + 1,
+            _ => i += 1,
+        }
+    }
+    bytes.len()
+}
+
+/// Whether the `r` at `start` opens a raw string such as `r"..."`, `r#"..."#` or `br"..."`
+fn starts_raw_string(bytes: &[u8], start: usize) -> bool {
+    let prefix_ok = match start.checked_sub(1).map(|i| bytes[i]) {
+        None => true,
+        Some(b'b') => start < 2 || !is_ident_byte(bytes[start - 2]),
+        Some(prev) => !is_ident_byte(prev),
+    };
+    let after_hashes = bytes[start + 1..].iter().find(|&&b| b != b'#');
+    prefix_ok && after_hashes == Some(&b'"')
+}
+
+fn raw_string_end(bytes: &[u8], start: usize) -> usize {
+    let hashes = bytes[start + 1..]
+        .iter()
+        .take_while(|&&b| b == b'#')
+        .count();
+    let body = start + 1 + hashes + 1;
+    let mut i = body;
+    while i < bytes.len() {
+        if bytes[i] == b'"'
+            && bytes[i + 1..]
+                .iter()
+                .take(hashes)
+                .filter(|&&b| b == b'#')
+                .count()
+                == hashes
+        {
+            return i + 1 + hashes;
+        }
+        i += 1;
+    }
+    bytes.len()
+}
+
+/// The end of a character literal at `start`, or `None` when the quote begins a lifetime
+fn char_literal_end(source: &str, start: usize) -> Option<usize> {
+    let bytes = source.as_bytes();
+    if bytes.get(start + 1) == Some(&b'\\') {
+        let close = bytes.get(start + 3..)?.iter().position(|&b| b == b'\'')?;
+        return Some(start + 3 + close + 1);
+    }
+    let c = source.get(start + 1..)?.chars().next()?;
+    let close = start + 1 + c.len_utf8();
+    (bytes.get(close) == Some(&b'\'')).then_some(close + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::Scanner;
+
+    /// Mask `source`, checking that `#[untested]` survives only where it is real code
+    ///
+    /// Returns the lines the scanner reports `untested` on.
+    fn check(source: &str, real_marker: &str) -> Vec<usize> {
+        let code = code_only(source);
+
+        assert_eq!(code.len(), source.len());
+        let breaks =
+            |text: &str| -> Vec<usize> { text.match_indices('\n').map(|(at, _)| at).collect() };
+        assert_eq!(breaks(&code), breaks(source));
+
+        let at = source.find(real_marker).expect("fixture has a real marker");
+        assert_eq!(&code[at..at + real_marker.len()], real_marker);
+        assert_eq!(code.matches("#[untested]").count(), 1, "{:?}", code);
+
+        let file = Path::new("src/lib.rs");
+        Scanner::new()
+            .scan_source(file, file, source)
+            .into_iter()
+            .filter(|i| i.macro_name == "untested")
+            .map(|i| i.line)
+            .collect()
+    }
+
+    #[test]
+    fn line_comments_are_blanked() {
+        let source =
+            "// #[untested] in a comment\nfn a() {} // #[untested]\n#[untested]\nfn b() {}\n";
+
+        assert_eq!(check(source, "#[untested]\nfn b"), [3]);
+    }
+
+    #[test]
+    fn nested_block_comments_are_blanked() {
+        let source =
+            "/* outer /* #[untested] */\n#[untested] still inside */\n#[untested]\nfn b() {}\n";
+
+        assert_eq!(check(source, "#[untested]\nfn b"), [3]);
+    }
+
+    #[test]
+    fn string_literals_are_blanked() {
+        let source = "const A: &str = \"#[untested] \\\" #[untested]\";\n#[untested]\nfn b() {}\n";
+
+        assert_eq!(check(source, "#[untested]\nfn b"), [2]);
+    }
+
+    #[test]
+    fn raw_string_literals_are_blanked() {
+        let source = "const A: &str = r##\"\n\"#[untested]\n\"##;\nconst B: &[u8] = br\"#[untested]\";\n#[untested]\nfn b() {}\n";
+
+        assert_eq!(check(source, "#[untested]\nfn b"), [5]);
+    }
+
+    #[test]
+    fn char_literals_are_blanked_but_lifetimes_kept() {
+        let source = "const Q: char = '\"';\nconst U: char = 'ü';\n#[untested]\nfn b<'a>(s: &'a str) -> &'a str { s }\nconst E: char = '\\'';\n";
+        let code = code_only(source);
+
+        assert_eq!(check(source, "#[untested]\nfn b<'a>(s: &'a str)"), [3]);
+        assert!(code.contains("const Q: char =    ;"), "{:?}", code);
+        assert!(code.contains("const E: char =     ;"), "{:?}", code);
+    }
+}
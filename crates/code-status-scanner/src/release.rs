@@ -0,0 +1,219 @@
+//! Release-readiness scoring from unresolved release-blocking markers.
+
+use colored::Colorize;
+
+use crate::{parse_macro_name, validate, MacroInstance};
+
+/// Score a release starts from before penalties
+const FULL_SCORE: u32 = 100;
+
+/// Markers that block a release, with their default penalty
+const DEFAULT_PENALTIES: &[(&str, u32)] = &[
+    ("breaking_change", 25),
+    ("requires_migration", 25),
+    ("requires_schema_migration_test", 25),
+    ("requires_rollback_plan", 25),
+    ("needs_benchmark_before_merge", 10),
+];
+
+/// Overrides the penalty of one blocking marker
+#[derive(Debug, Clone)]
+pub struct Penalty {
+    macro_name: String,
+    points: u32,
+}
+
+impl Penalty {
+    pub fn macro_name(&self) -> &str {
+        &self.macro_name
+    }
+}
+
+/// Parse a `--penalty` argument of the form `macro=points`
+pub fn parse_penalty(spec: &str) -> Result<Penalty, String> {
+    let (macro_name, points) = spec
+        .split_once('=')
+        .ok_or_else(|| format!("expected `macro=points`, got `{}`", spec))?;
+    let macro_name = parse_macro_name(macro_name)?;
+    let points = points
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid penalty `{}` for `{}`", points.trim(), macro_name))?;
+
+    Ok(Penalty { macro_name, points })
+}
+
+/// Markers that still block the release
+///
+/// A `requires_migration` is resolved once a matching `migration_complete`
+/// exists; the other blocking markers are resolved by removing them.
+fn blocking_items<'a>(
+    instances: &'a [MacroInstance],
+    penalties: &[(String, u32)],
+) -> Vec<&'a MacroInstance> {
+    let pending_migrations = validate::pending_migrations(instances);
+
+    instances
+        .iter()
+        .filter(|i| penalties.iter().any(|(name, _)| *name == i.macro_name))
+        .filter(|i| {
+            i.macro_name != "requires_migration"
+                || pending_migrations.iter().any(|p| std::ptr::eq(*p, *i))
+        })
+        .collect()
+}
+
+/// The readiness score and the blocking items, each with the points it costs
+fn assess<'a>(
+    instances: &'a [MacroInstance],
+    overrides: &[Penalty],
+) -> (u32, Vec<(&'a MacroInstance, u32)>) {
+    let mut penalties: Vec<(String, u32)> = DEFAULT_PENALTIES
+        .iter()
+        .map(|(name, points)| (name.to_string(), *points))
+        .collect();
+    for penalty in overrides {
+        match penalties
+            .iter_mut()
+            .find(|(name, _)| *name == penalty.macro_name)
+        {
+            Some(entry) => entry.1 = penalty.points,
+            None => penalties.push((penalty.macro_name.clone(), penalty.points)),
+        }
+    }
+
+    let penalty_of = |name: &str| {
+        penalties
+            .iter()
+            .find(|(n, _)| n == name)
+            .map_or(0, |(_, points)| *points)
+    };
+    let blocking: Vec<_> = blocking_items(instances, &penalties)
+        .into_iter()
+        .map(|instance| (instance, penalty_of(&instance.macro_name)))
+        .collect();
+    let total_penalty: u32 = blocking.iter().map(|(_, points)| points).sum();
+
+    (FULL_SCORE.saturating_sub(total_penalty), blocking)
+}
+
+/// Print the readiness score with the items blocking the release
+///
+/// Returns `true` when the score reaches `min_score`.
+pub fn report(instances: &[MacroInstance], overrides: &[Penalty], min_score: u32) -> bool {
+    let (score, blocking) = assess(instances, overrides);
+    let ready = score >= min_score;
+
+    let verdict = if ready {
+        "PASS".green().bold()
+    } else {
+        "FAIL".red().bold()
+    };
+    println!(
+        "Release readiness: {}/{} (minimum {}) {}",
+        score, FULL_SCORE, min_score, verdict
+    );
+
+    if blocking.is_empty() {
+        println!();
+        println!("{}", "No release-blocking markers found.".green());
+        return ready;
+    }
+
+    println!();
+    println!("Blocking items ({}):", blocking.len());
+    for (instance, points) in blocking {
+        println!(
+            "{} {} {}",
+            format!("{}:{}", instance.path.display(), instance.line).blue(),
+            format!("#[{}]", instance.macro_name).yellow(),
+            format!("-{}", points).red()
+        );
+        if let Some(description) = instance.argument_value() {
+            println!("    {}", description);
+        }
+        println!("    {}", instance.context_block("    "));
+    }
+
+    ready
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use code_status_scanner::Scanner;
+
+    use super::*;
+
+    fn scan(source: &str) -> Vec<MacroInstance> {
+        let file = Path::new("src/lib.rs");
+        Scanner::new().scan_source(file, file, source)
+    }
+
+    #[test]
+    fn release_with_blocking_markers_is_penalized() {
+        let instances = scan(
+            r#"
+#[breaking_change("removed Config::legacy")]
+struct Config {}
+
+#[requires_migration("split users table")]
+fn migrate_users() {}
+
+#[requires_schema_migration_test]
+fn add_column() {}
+
+#[requires_rollback_plan("restore the old index")]
+#[needs_benchmark_before_merge("index rebuild throughput")]
+fn rebuild_index() {}
+
+#[needs("docs")]
+fn helper() {}
+"#,
+        );
+
+        let (score, blocking) = assess(&instances, &[]);
+        let names: Vec<_> = blocking
+            .iter()
+            .map(|(i, points)| (i.macro_name.as_str(), *points))
+            .collect();
+        assert_eq!(
+            names,
+            [
+                ("breaking_change", 25),
+                ("requires_migration", 25),
+                ("requires_schema_migration_test", 25),
+                ("requires_rollback_plan", 25),
+                ("needs_benchmark_before_merge", 10)
+            ]
+        );
+        assert_eq!(score, 0);
+
+        let overrides = [
+            parse_penalty("requires_migration=0").unwrap(),
+            parse_penalty("requires_rollback_plan=5").unwrap(),
+        ];
+        assert_eq!(assess(&instances, &overrides).0, 35);
+    }
+
+    #[test]
+    fn release_without_blocking_markers_is_ready() {
+        let instances = scan(
+            r#"
+#[requires_migration("split users table")]
+fn migrate_users() {}
+
+#[migration_complete("split users table")]
+fn users_split() {}
+
+#[needs("docs")]
+fn helper() {}
+"#,
+        );
+
+        let (score, blocking) = assess(&instances, &[]);
+        assert!(blocking.is_empty());
+        assert_eq!(score, FULL_SCORE);
+    }
+}
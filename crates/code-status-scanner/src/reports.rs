@@ -0,0 +1,2151 @@
+//! Focused reports built on top of individual marker types.
+
+use std::{collections::BTreeMap, fs, path::Path};
+
+use colored::{ColoredString, Colorize};
+use serde::Serialize;
+use walkdir::WalkDir;
+
+use crate::{date::Date, MacroInstance};
+
+/// List code scheduled for removal, ordered by sunset date
+///
+/// Entries whose sunset date is on or before `today` are flagged in red.
+pub fn list_sunsets(instances: &[MacroInstance], today: Date) {
+    let (scheduled, malformed) = sunset_schedule(instances);
+
+    if scheduled.is_empty() && malformed.is_empty() {
+        println!("{}", "No features scheduled for sunset.".yellow());
+        return;
+    }
+
+    let past_due = scheduled
+        .iter()
+        .filter(|(date, _)| is_past_due(*date, today))
+        .count();
+
+    println!(
+        "{}",
+        format!(
+            "Found {} features scheduled for sunset ({} past due as of {}):",
+            scheduled.len(),
+            past_due,
+            today
+        )
+        .green()
+    );
+    println!();
+
+    for (date, instance) in &scheduled {
+        let date_display = if is_past_due(*date, today) {
+            format!("{} (past due)", date).red().bold()
+        } else {
+            date.to_string().yellow()
+        };
+
+        println!(
+            "{} {}",
+            date_display,
+            format!("{}:{}", instance.path.display(), instance.line).blue()
+        );
+        println!("    {}", instance.context_block("    "));
+        println!();
+    }
+
+    for instance in &malformed {
+        println!(
+            "{} {}",
+            "invalid sunset date".red(),
+            format!("{}:{}", instance.path.display(), instance.line).blue()
+        );
+        println!("    {}", instance.context_block("    "));
+        println!();
+    }
+}
+
+/// `requires_feature_sunset` markers ordered by date, and those whose date doesn't parse
+fn sunset_schedule(
+    instances: &[MacroInstance],
+) -> (Vec<(Date, &MacroInstance)>, Vec<&MacroInstance>) {
+    let mut scheduled = Vec::new();
+    let mut malformed = Vec::new();
+
+    for instance in instances
+        .iter()
+        .filter(|i| i.macro_name == "requires_feature_sunset")
+    {
+        match instance.argument_value().and_then(Date::parse) {
+            Some(date) => scheduled.push((date, instance)),
+            None => malformed.push(instance),
+        }
+    }
+    scheduled.sort_by_key(|(date, _)| *date);
+
+    (scheduled, malformed)
+}
+
+/// Whether a feature sunsetting on `date` should already be gone by `today`
+fn is_past_due(date: Date, today: Date) -> bool {
+    date <= today
+}
+
+/// A backward-compatibility shim with what is known about its removal
+struct Shim<'a> {
+    shim: &'a MacroInstance,
+    /// Version from a `deprecated_since` marker on the same item
+    deprecated_since: Option<&'a str>,
+    /// Date from a `requires_feature_sunset` marker on the same item
+    sunset: Option<Date>,
+}
+
+impl Shim<'_> {
+    fn has_removal_timing(&self) -> bool {
+        self.deprecated_since.is_some() || self.sunset.is_some()
+    }
+}
+
+/// Every `legacy_compat` shim, cross-referenced with the deprecation and
+/// sunset markers on the same item
+fn compat_shims(instances: &[MacroInstance]) -> Vec<Shim<'_>> {
+    let on_item = |shim: &MacroInstance, macro_name: &str| {
+        instances
+            .iter()
+            .filter(|i| i.macro_name == macro_name && i.same_item(shim))
+            .filter_map(|i| i.argument_value())
+            .next()
+    };
+
+    marked(instances, "legacy_compat")
+        .into_iter()
+        .map(|shim| Shim {
+            shim,
+            deprecated_since: on_item(shim, "deprecated_since"),
+            sunset: on_item(shim, "requires_feature_sunset").and_then(Date::parse),
+        })
+        .collect()
+}
+
+/// List backward-compatibility shims alongside their planned removal
+///
+/// Removal timing comes from `deprecated_since` and `requires_feature_sunset`
+/// markers on the same item.
+pub fn compat_surface(instances: &[MacroInstance]) {
+    let shims = compat_shims(instances);
+
+    if shims.is_empty() {
+        println!("{}", "No compatibility shims found.".yellow());
+        return;
+    }
+
+    println!(
+        "{}",
+        format!("Found {} compatibility shims:", shims.len()).green()
+    );
+    println!();
+
+    for shim in &shims {
+        let removal = match (shim.deprecated_since, shim.sunset) {
+            (Some(version), Some(date)) => {
+                format!("deprecated since {}, removal planned {}", version, date).cyan()
+            },
+            (Some(version), None) => format!("deprecated since {}", version).cyan(),
+            (None, Some(date)) => format!("removal planned {}", date).cyan(),
+            (None, None) => "no removal timing".red(),
+        };
+
+        println!(
+            "{} {} [{}]",
+            format!("{}:{}", shim.shim.path.display(), shim.shim.line).blue(),
+            shim.shim.argument_value().unwrap_or_default().yellow(),
+            removal
+        );
+        println!("    {}", shim.shim.context_block("    "));
+        println!();
+    }
+
+    let timed = shims.iter().filter(|s| s.has_removal_timing()).count();
+    println!(
+        "{}",
+        format!(
+            "{} of {} shims are deprecated or have a planned removal date.",
+            timed,
+            shims.len()
+        )
+        .cyan()
+    );
+}
+
+/// Markers that mean an item is expected to go away
+const REMOVAL_MARKERS: &[&str] = &[
+    "temporary",
+    "requires_feature_sunset",
+    "legacy_compat",
+    "deprecated_since",
+];
+
+/// Every `replaces` marker with the removal marker found on the replaced item
+///
+/// The replaced item is looked up by name among annotated items.
+fn replacements(instances: &[MacroInstance]) -> Vec<(&MacroInstance, Option<&MacroInstance>)> {
+    marked(instances, "replaces")
+        .into_iter()
+        .map(|pair| {
+            let replaced = pair.argument_value().unwrap_or_default();
+            let removal = instances.iter().find(|i| {
+                REMOVAL_MARKERS.contains(&i.macro_name.as_str())
+                    && reference_name(i) == Some(replaced)
+            });
+            (pair, removal)
+        })
+        .collect()
+}
+
+/// List `replaces` pairs, showing whether each replaced item is marked for removal
+///
+/// When the replaced item carries a removal marker (`temporary`,
+/// `requires_feature_sunset`, `legacy_compat` or `deprecated_since`) that
+/// marker's location is shown.
+pub fn replacement_pairs(instances: &[MacroInstance]) {
+    let pairs = replacements(instances);
+
+    if pairs.is_empty() {
+        println!("{}", "No replacement pairs found.".yellow());
+        return;
+    }
+
+    println!(
+        "{}",
+        format!("Found {} replacement pairs:", pairs.len()).green()
+    );
+    println!();
+
+    let mut unmarked = 0;
+    for (pair, removal) in &pairs {
+        let replaced = pair.argument_value().unwrap_or_default();
+        let replacement = pair.item_name.as_deref().unwrap_or("<unknown item>");
+
+        println!(
+            "{} {} {} {}",
+            replacement.green(),
+            "replaces".dimmed(),
+            replaced.yellow(),
+            format!("({}:{})", pair.path.display(), pair.line).blue()
+        );
+
+        match removal {
+            Some(marker) => println!(
+                "    {} {}",
+                format!("old item marked #[{}] at", marker.macro_name).cyan(),
+                format!("{}:{}", marker.path.display(), marker.line).blue()
+            ),
+            None => {
+                unmarked += 1;
+                println!("    {}", "old item is not marked for removal".red());
+            },
+        }
+        println!();
+    }
+
+    if unmarked > 0 {
+        println!(
+            "{}",
+            format!(
+                "{} replaced items lack a temporary, requires_feature_sunset, legacy_compat or deprecated_since marker.",
+                unmarked
+            )
+            .yellow()
+        );
+    }
+}
+
+/// Markers describing how a function fails
+const ERROR_PATH_MARKERS: &[&str] = &["panic_path", "includes_unwrap", "requires_cleanup_on_error"];
+
+/// Group error-path concerns per annotated function
+///
+/// A function that can panic (`panic_path`) and also must clean up on error
+/// (`requires_cleanup_on_error`) is flagged as high risk, since a panic skips
+/// the cleanup path entirely.
+pub fn error_handling(instances: &[MacroInstance]) {
+    let functions = error_paths(instances);
+
+    if functions.is_empty() {
+        println!("{}", "No error-handling concerns found.".yellow());
+        return;
+    }
+
+    let high_risk = functions.values().filter(|m| is_high_risk(m)).count();
+
+    println!(
+        "{}",
+        format!(
+            "Found error-handling concerns on {} items ({} high risk):",
+            functions.len(),
+            high_risk
+        )
+        .green()
+    );
+    println!();
+
+    for ((file, item), markers) in &functions {
+        let header = format!("{} ({}:{})", item, file, markers[0].line);
+        if is_high_risk(markers) {
+            println!(
+                "{} {}",
+                header.bold(),
+                "HIGH RISK: can panic before cleanup runs".red().bold()
+            );
+        } else {
+            println!("{}", header.bold());
+        }
+        for marker in markers {
+            println!(
+                "    {} {}",
+                format!("#[{}]", marker.macro_name).green(),
+                marker.argument_value().unwrap_or_default().yellow()
+            );
+        }
+        println!();
+    }
+}
+
+/// Error-path markers keyed by `(file, item)` of the function they annotate
+fn error_paths(instances: &[MacroInstance]) -> BTreeMap<(String, String), Vec<&MacroInstance>> {
+    let mut functions: BTreeMap<(String, String), Vec<&MacroInstance>> = BTreeMap::new();
+    for instance in instances
+        .iter()
+        .filter(|i| ERROR_PATH_MARKERS.contains(&i.macro_name.as_str()))
+    {
+        let item = instance
+            .item_name
+            .clone()
+            .unwrap_or_else(|| format!("<line {}>", instance.line));
+        functions
+            .entry((instance.path.display().to_string(), item))
+            .or_default()
+            .push(instance);
+    }
+    functions
+}
+
+/// Whether a function's error-path markers say it can panic before its cleanup runs
+fn is_high_risk(markers: &[&MacroInstance]) -> bool {
+    let has = |name: &str| markers.iter().any(|i| i.macro_name == name);
+    has("panic_path") && has("requires_cleanup_on_error")
+}
+
+/// Markers describing concurrency hazards
+pub const CONCURRENCY_MARKERS: &[&str] = &[
+    "deadlock_risk",
+    "data_race_risk",
+    "not_thread_safe",
+    "blocking_io",
+    "requires_ordering",
+];
+
+/// Report every concurrency hazard, grouped by the kind of hazard
+pub fn concurrency(instances: &[MacroInstance]) {
+    print_grouped(
+        &concurrency_groups(instances),
+        "hazard type",
+        "concurrency hazards",
+    );
+}
+
+/// Concurrency markers keyed by macro name
+fn concurrency_groups(instances: &[MacroInstance]) -> BTreeMap<String, Vec<&MacroInstance>> {
+    let mut groups: BTreeMap<String, Vec<&MacroInstance>> = BTreeMap::new();
+    for instance in instances
+        .iter()
+        .filter(|i| CONCURRENCY_MARKERS.contains(&i.macro_name.as_str()))
+    {
+        groups
+            .entry(instance.macro_name.clone())
+            .or_default()
+            .push(instance);
+    }
+    groups
+}
+
+/// Group authorization checkpoints by permission and flag unguarded sensitive items
+///
+/// A `security_sensitive` item counts as guarded when the same item also
+/// carries `requires_permission_check`.
+pub fn authz(instances: &[MacroInstance]) {
+    print_grouped(
+        &permission_groups(instances),
+        "permission",
+        "permission checkpoints",
+    );
+
+    let unguarded = unguarded(instances);
+
+    if unguarded.is_empty() {
+        return;
+    }
+
+    println!(
+        "{}",
+        format!(
+            "{} security_sensitive items have no requires_permission_check:",
+            unguarded.len()
+        )
+        .red()
+        .bold()
+    );
+    println!();
+    for instance in unguarded {
+        println!(
+            "{}",
+            format!("{}:{}", instance.path.display(), instance.line).blue()
+        );
+        println!("    {}", instance.context_block("    "));
+        println!();
+    }
+}
+
+/// `requires_permission_check` markers keyed by the permission they require
+fn permission_groups(instances: &[MacroInstance]) -> BTreeMap<String, Vec<&MacroInstance>> {
+    let mut groups: BTreeMap<String, Vec<&MacroInstance>> = BTreeMap::new();
+    for check in marked(instances, "requires_permission_check") {
+        let permission = check.argument_value().unwrap_or("unspecified").to_string();
+        groups.entry(permission).or_default().push(check);
+    }
+    groups
+}
+
+/// `security_sensitive` items without a `requires_permission_check` on the same item
+fn unguarded(instances: &[MacroInstance]) -> Vec<&MacroInstance> {
+    let checks = marked(instances, "requires_permission_check");
+    marked(instances, "security_sensitive")
+        .into_iter()
+        .filter(|sensitive| !checks.iter().any(|check| check.same_item(sensitive)))
+        .collect()
+}
+
+/// Inventory the distinct secrets named by `requires_secret` with their usage sites
+///
+/// Sites whose item is not also marked `security_sensitive` are flagged, since
+/// code handling a secret usually warrants that marker too.
+pub fn secrets(instances: &[MacroInstance]) {
+    let secrets = secret_inventory(instances);
+
+    if secrets.is_empty() {
+        println!("{}", "No requires_secret markers found.".yellow());
+        return;
+    }
+
+    let is_sensitive = |site: &MacroInstance| is_security_sensitive(instances, site);
+
+    let sites: usize = secrets.values().map(Vec::len).sum();
+    println!(
+        "{}",
+        format!(
+            "Found {} distinct secrets referenced from {} sites:",
+            secrets.len(),
+            sites
+        )
+        .green()
+    );
+    println!();
+
+    let mut unmarked = 0;
+    for (name, users) in &secrets {
+        println!("{}", format!("{} ({})", name, users.len()).cyan().bold());
+        for site in users {
+            let location = format!("{}:{}", site.path.display(), site.line).blue();
+            if is_sensitive(site) {
+                println!("  {}", location);
+            } else {
+                unmarked += 1;
+                println!("  {} {}", location, "not security_sensitive".red());
+            }
+            println!("      {}", site.context_block("      "));
+        }
+        println!();
+    }
+
+    if unmarked > 0 {
+        println!(
+            "{}",
+            format!(
+                "{} secret usage sites are not marked security_sensitive.",
+                unmarked
+            )
+            .red()
+            .bold()
+        );
+    }
+}
+
+/// `requires_secret` usage sites keyed by the distinct secret they name
+fn secret_inventory(instances: &[MacroInstance]) -> BTreeMap<&str, Vec<&MacroInstance>> {
+    let mut secrets: BTreeMap<&str, Vec<&MacroInstance>> = BTreeMap::new();
+    for instance in marked(instances, "requires_secret") {
+        let name = instance.argument_value().unwrap_or("unnamed").trim();
+        secrets.entry(name).or_default().push(instance);
+    }
+    secrets
+}
+
+/// Whether `site`'s item is also marked `security_sensitive`
+fn is_security_sensitive(instances: &[MacroInstance], site: &MacroInstance) -> bool {
+    instances
+        .iter()
+        .any(|i| i.macro_name == "security_sensitive" && i.same_item(site))
+}
+
+/// Check `requires_review_before` deadlines, listing the overdue ones
+///
+/// A deadline on or before `today` is overdue. Returns `true` when none are.
+pub fn review_gate(instances: &[MacroInstance], today: Date) -> bool {
+    let overdue = overdue_reviews(instances, today);
+
+    if overdue.is_empty() {
+        println!(
+            "{}",
+            format!("No review deadlines have passed as of {}.", today).green()
+        );
+        return true;
+    }
+
+    println!(
+        "{}",
+        format!("{} reviews are overdue as of {}:", overdue.len(), today)
+            .red()
+            .bold()
+    );
+    println!();
+
+    for (deadline, instance) in overdue {
+        println!(
+            "{} {}",
+            format!("due {}", deadline).red(),
+            format!("{}:{}", instance.path.display(), instance.line).blue()
+        );
+        println!("    {}", instance.context_block("    "));
+        println!();
+    }
+
+    false
+}
+
+/// `requires_review_before` deadlines on or before `today`, earliest first
+fn overdue_reviews(instances: &[MacroInstance], today: Date) -> Vec<(Date, &MacroInstance)> {
+    let mut overdue: Vec<_> = marked(instances, "requires_review_before")
+        .into_iter()
+        .filter_map(|i| i.argument_value().and_then(Date::parse).map(|d| (d, i)))
+        .filter(|(deadline, _)| *deadline <= today)
+        .collect();
+    overdue.sort_by_key(|(deadline, _)| *deadline);
+    overdue
+}
+
+/// Group `numeric_precision` decisions by the numeric type they concern
+pub fn precision(instances: &[MacroInstance]) {
+    print_grouped(
+        &precision_groups(instances),
+        "numeric type",
+        "precision decisions",
+    );
+}
+
+/// `numeric_precision` markers keyed by the numeric type their note names
+fn precision_groups(instances: &[MacroInstance]) -> BTreeMap<String, Vec<&MacroInstance>> {
+    let mut groups: BTreeMap<String, Vec<&MacroInstance>> = BTreeMap::new();
+    for instance in marked(instances, "numeric_precision") {
+        let numeric_type = precision_type(instance.argument_value().unwrap_or_default());
+        groups
+            .entry(numeric_type.to_string())
+            .or_default()
+            .push(instance);
+    }
+    groups
+}
+
+/// The numeric type a precision note mentions first: "f32 sufficient" -> f32
+fn precision_type(note: &str) -> &'static str {
+    const TYPES: &[(&str, &str)] = &[
+        ("f16", "f16"),
+        ("f32", "f32"),
+        ("f64", "f64"),
+        ("f128", "f128"),
+        ("fixed", "fixed-point"),
+        ("decimal", "decimal"),
+    ];
+
+    let note = note.to_lowercase();
+    TYPES
+        .iter()
+        .filter_map(|(needle, name)| note.find(needle).map(|at| (at, *name)))
+        .min_by_key(|(at, _)| *at)
+        .map_or("other", |(_, name)| name)
+}
+
+/// Group circuit-breaker requirements by dependency and list unprotected dependency-sensitive code
+///
+/// A `dependency_sensitive` item counts as covered when the same item also
+/// carries `requires_circuit_breaker`.
+pub fn resilience(instances: &[MacroInstance]) {
+    print_grouped(
+        &breaker_groups(instances),
+        "dependency",
+        "circuit breaker requirements",
+    );
+
+    let uncovered = unprotected(instances);
+
+    if uncovered.is_empty() {
+        return;
+    }
+
+    println!(
+        "{}",
+        format!(
+            "{} dependency_sensitive items have no requires_circuit_breaker:",
+            uncovered.len()
+        )
+        .yellow()
+        .bold()
+    );
+    println!();
+    for instance in uncovered {
+        println!(
+            "{}",
+            format!("{}:{}", instance.path.display(), instance.line).blue()
+        );
+        println!("    {}", instance.context_block("    "));
+        println!();
+    }
+}
+
+/// `requires_circuit_breaker` markers keyed by the dependency they protect against
+fn breaker_groups(instances: &[MacroInstance]) -> BTreeMap<String, Vec<&MacroInstance>> {
+    let mut groups: BTreeMap<String, Vec<&MacroInstance>> = BTreeMap::new();
+    for breaker in marked(instances, "requires_circuit_breaker") {
+        let dependency = breaker.argument_value().unwrap_or("unspecified").trim();
+        groups
+            .entry(dependency.to_string())
+            .or_default()
+            .push(breaker);
+    }
+    groups
+}
+
+/// `dependency_sensitive` items without a `requires_circuit_breaker` on the same item
+fn unprotected(instances: &[MacroInstance]) -> Vec<&MacroInstance> {
+    let breakers = marked(instances, "requires_circuit_breaker");
+    marked(instances, "dependency_sensitive")
+        .into_iter()
+        .filter(|sensitive| !breakers.iter().any(|breaker| breaker.same_item(sensitive)))
+        .collect()
+}
+
+/// Group `requires_load_test` markers by the milestone they must be load tested before
+pub fn load_test_gate(instances: &[MacroInstance]) {
+    print_grouped(
+        &milestone_groups(instances),
+        "milestone",
+        "paths needing a load test",
+    );
+}
+
+/// `requires_load_test` markers keyed by milestone
+fn milestone_groups(instances: &[MacroInstance]) -> BTreeMap<String, Vec<&MacroInstance>> {
+    let mut groups: BTreeMap<String, Vec<&MacroInstance>> = BTreeMap::new();
+    for instance in marked(instances, "requires_load_test") {
+        let milestone = instance.argument_value().unwrap_or("unspecified").trim();
+        groups
+            .entry(milestone.to_string())
+            .or_default()
+            .push(instance);
+    }
+    groups
+}
+
+/// Group `requires_chaos_test` markers by failure scenario, ignoring case and spacing
+pub fn chaos(instances: &[MacroInstance]) {
+    print_grouped(
+        &scenario_groups(instances),
+        "failure scenario",
+        "chaos experiments",
+    );
+}
+
+/// `requires_chaos_test` markers keyed by lowercased, space-collapsed scenario
+fn scenario_groups(instances: &[MacroInstance]) -> BTreeMap<String, Vec<&MacroInstance>> {
+    let mut groups: BTreeMap<String, Vec<&MacroInstance>> = BTreeMap::new();
+    for instance in marked(instances, "requires_chaos_test") {
+        let scenario = instance
+            .argument_value()
+            .unwrap_or("unspecified")
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_lowercase();
+        groups.entry(scenario).or_default().push(instance);
+    }
+    groups
+}
+
+/// Markers showing a path is, or will be, instrumented
+const OBSERVABILITY_MARKERS: &[&str] = &["observed", "requires_metrics"];
+
+/// List metrics requirements and the performance-critical paths without any
+///
+/// A `perf_critical` item counts as covered when the same item also carries
+/// `observed` or `requires_metrics`.
+pub fn observability(instances: &[MacroInstance]) {
+    list_marker(instances, "requires_metrics", "metrics requirements");
+
+    let unobserved = unobserved(instances);
+
+    if unobserved.is_empty() {
+        return;
+    }
+
+    println!(
+        "{}",
+        format!(
+            "{} perf_critical items have neither observed nor requires_metrics:",
+            unobserved.len()
+        )
+        .yellow()
+        .bold()
+    );
+    println!();
+    for instance in unobserved {
+        println!(
+            "{}",
+            format!("{}:{}", instance.path.display(), instance.line).blue()
+        );
+        println!("    {}", instance.context_block("    "));
+        println!();
+    }
+}
+
+/// `perf_critical` items with no observability marker on the same item
+fn unobserved(instances: &[MacroInstance]) -> Vec<&MacroInstance> {
+    let instrumented: Vec<_> = instances
+        .iter()
+        .filter(|i| OBSERVABILITY_MARKERS.contains(&i.macro_name.as_str()))
+        .collect();
+    marked(instances, "perf_critical")
+        .into_iter()
+        .filter(|critical| !instrumented.iter().any(|i| i.same_item(critical)))
+        .collect()
+}
+
+/// Group `requires_input_sanitization` markers by injection class
+///
+/// Classes are matched case-insensitively and shown in their canonical spelling.
+pub fn injection_surface(instances: &[MacroInstance]) {
+    print_grouped(
+        &injection_groups(instances),
+        "injection class",
+        "untrusted input handlers",
+    );
+}
+
+/// `requires_input_sanitization` markers keyed by canonical injection class
+fn injection_groups(instances: &[MacroInstance]) -> BTreeMap<String, Vec<&MacroInstance>> {
+    const CLASSES: &[&str] = &["SQL", "shell", "HTML", "path", "log"];
+
+    let mut groups: BTreeMap<String, Vec<&MacroInstance>> = BTreeMap::new();
+    for instance in marked(instances, "requires_input_sanitization") {
+        let class = instance.argument_value().unwrap_or_default().trim();
+        let class = CLASSES
+            .iter()
+            .find(|known| known.eq_ignore_ascii_case(class))
+            .map_or_else(|| class.to_string(), |known| known.to_string());
+        groups.entry(class).or_default().push(instance);
+    }
+    groups
+}
+
+/// Group capacity-planning markers by the dimension their cost scales with
+pub fn capacity(instances: &[MacroInstance]) {
+    print_grouped(
+        &capacity_groups(instances),
+        "scaling dimension",
+        "capacity planning items",
+    );
+}
+
+/// `requires_capacity_planning` markers keyed by scaling dimension
+fn capacity_groups(instances: &[MacroInstance]) -> BTreeMap<String, Vec<&MacroInstance>> {
+    let mut groups: BTreeMap<String, Vec<&MacroInstance>> = BTreeMap::new();
+    for instance in instances
+        .iter()
+        .filter(|i| i.macro_name == "requires_capacity_planning")
+    {
+        let dimension = scaling_dimension(instance.argument_value().unwrap_or_default());
+        groups.entry(dimension).or_default().push(instance);
+    }
+    groups
+}
+
+/// Report fuzzing requirements that no fuzz target references
+///
+/// An item counts as covered when its name appears as a whole word in any
+/// `.rs` file under `fuzz_dir`.
+pub fn fuzz_gaps(instances: &[MacroInstance], fuzz_dir: &Path) {
+    let targets = read_rust_sources(fuzz_dir);
+    report_reference_gaps(
+        instances,
+        "requires_fuzzing",
+        &targets,
+        "fuzz target",
+        fuzz_dir,
+    );
+}
+
+/// Report backward-compat test requirements that no test file references
+pub fn compat_test_gaps(instances: &[MacroInstance], tests_dir: &Path) {
+    let tests = read_rust_sources(tests_dir);
+    report_reference_gaps(
+        instances,
+        "requires_backward_compat_test",
+        &tests,
+        "test",
+        tests_dir,
+    );
+}
+
+/// Report property-test requirements that no property test references
+///
+/// Only test files using proptest or quickcheck are searched, so a plain unit
+/// test mentioning the item doesn't count as coverage.
+pub fn property_test_gaps(instances: &[MacroInstance], tests_dir: &Path) {
+    let tests = read_property_tests(tests_dir);
+    report_reference_gaps(
+        instances,
+        "requires_property_test",
+        &tests,
+        "property test",
+        tests_dir,
+    );
+}
+
+/// Read the `.rs` files under `dir` that use proptest or quickcheck
+fn read_property_tests(dir: &Path) -> Vec<String> {
+    read_rust_sources(dir)
+        .into_iter()
+        .filter(|source| source.contains("proptest") || source.contains("quickcheck"))
+        .collect()
+}
+
+/// Totals of the `needs_tests` markers, a burn-down of tests still to write
+#[derive(Debug, Default, Serialize)]
+pub struct TestBurndown {
+    /// Markers whose counts could be read
+    pub items: usize,
+    pub have: u64,
+    pub want: u64,
+    pub missing: u64,
+}
+
+/// Add up the test counts of every `needs_tests` marker
+pub fn test_burndown(instances: &[MacroInstance]) -> TestBurndown {
+    let mut burndown = TestBurndown::default();
+    for (have, want) in instances
+        .iter()
+        .filter(|i| i.macro_name == "needs_tests")
+        .filter_map(|i| i.argument_value().and_then(test_counts))
+    {
+        burndown.items += 1;
+        burndown.have += have;
+        burndown.want += want;
+        burndown.missing += want.saturating_sub(have);
+    }
+    burndown
+}
+
+/// The `(have, want)` counts of a `needs_tests` argument: `5` or `have = 2, want = 5`
+fn test_counts(argument: &str) -> Option<(u64, u64)> {
+    if let Ok(want) = argument.trim().parse() {
+        return Some((0, want));
+    }
+
+    let mut have = 0;
+    let mut want = None;
+    for pair in argument.split(',') {
+        let (key, value) = pair.split_once('=')?;
+        let value = value.trim().parse().ok()?;
+        match key.trim() {
+            "have" => have = value,
+            "want" => want = Some(value),
+            _ => return None,
+        }
+    }
+    Some((have, want?))
+}
+
+/// Report features marked `requires_feature_documentation` that the changelog doesn't mention
+///
+/// A feature counts as documented when the changelog mentions its item name as
+/// a whole word, or contains its note (case-insensitive), e.g. a ticket ID.
+pub fn release_notes_gaps(instances: &[MacroInstance], changelog: &Path) {
+    let text = match fs::read_to_string(changelog) {
+        Ok(text) => text,
+        Err(err) => {
+            println!(
+                "{}",
+                format!("Could not read {}: {}", changelog.display(), err).red()
+            );
+            return;
+        },
+    };
+    let (covered, uncovered) = release_note_coverage(instances, &text);
+
+    if covered.is_empty() && uncovered.is_empty() {
+        println!(
+            "{}",
+            "No #[requires_feature_documentation] markers found.".yellow()
+        );
+        return;
+    }
+
+    println!(
+        "{}",
+        format!(
+            "Checked {} #[requires_feature_documentation] items against {}:",
+            covered.len() + uncovered.len(),
+            changelog.display()
+        )
+        .green()
+    );
+    println!();
+
+    print_coverage(&covered, &uncovered, "release note");
+}
+
+/// Split `requires_feature_documentation` findings into documented and undocumented by `changelog`
+fn release_note_coverage<'a>(
+    instances: &'a [MacroInstance],
+    changelog: &str,
+) -> (Vec<&'a MacroInstance>, Vec<&'a MacroInstance>) {
+    let lowercase = changelog.to_lowercase();
+    marked(instances, "requires_feature_documentation")
+        .into_iter()
+        .partition(|i| {
+            reference_name(i).is_some_and(|name| mentions_identifier(changelog, name))
+                || i.argument_value()
+                    .map(|note| note.trim().to_lowercase())
+                    .is_some_and(|note| !note.is_empty() && lowercase.contains(&note))
+        })
+}
+
+/// Read every `.rs` file under `dir`, returning nothing when it doesn't exist
+fn read_rust_sources(dir: &Path) -> Vec<String> {
+    WalkDir::new(dir)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "rs"))
+        .filter_map(|entry| fs::read_to_string(entry.path()).ok())
+        .collect()
+}
+
+/// Whether `name` occurs in `text` as a whole identifier
+fn mentions_identifier(text: &str, name: &str) -> bool {
+    let is_ident_char = |c: char| c.is_alphanumeric() || c == '_';
+    text.match_indices(name).any(|(start, _)| {
+        let before = text[..start].chars().next_back();
+        let after = text[start + name.len()..].chars().next();
+        !before.is_some_and(is_ident_char) && !after.is_some_and(is_ident_char)
+    })
+}
+
+/// The identifier to look for when cross-referencing an annotated item
+///
+/// For `impl` blocks this is the last identifier of the header (the self type).
+fn reference_name(instance: &MacroInstance) -> Option<&str> {
+    instance
+        .item_name
+        .as_deref()?
+        .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .rfind(|t| !t.is_empty() && *t != "impl" && *t != "for")
+}
+
+/// Split one marker's findings into covered and uncovered by name reference
+///
+/// `sources` are the contents searched for references.
+fn reference_coverage<'a>(
+    instances: &'a [MacroInstance],
+    macro_name: &str,
+    sources: &[String],
+) -> (Vec<&'a MacroInstance>, Vec<&'a MacroInstance>) {
+    instances
+        .iter()
+        .filter(|i| i.macro_name == macro_name)
+        .partition(|i| {
+            reference_name(i)
+                .is_some_and(|name| sources.iter().any(|s| mentions_identifier(s, name)))
+        })
+}
+
+/// Print one marker's findings split into covered and uncovered by name reference
+///
+/// `sources` are the contents searched for references; `kind` names what they
+/// are (e.g. "fuzz target") and `location` where they were read from.
+fn report_reference_gaps(
+    instances: &[MacroInstance],
+    macro_name: &str,
+    sources: &[String],
+    kind: &str,
+    location: &Path,
+) {
+    let (covered, uncovered) = reference_coverage(instances, macro_name, sources);
+
+    if covered.is_empty() && uncovered.is_empty() {
+        println!(
+            "{}",
+            format!("No #[{}] markers found.", macro_name).yellow()
+        );
+        return;
+    }
+
+    println!(
+        "{}",
+        format!(
+            "Checked {} #[{}] items against {} {} files in {}:",
+            covered.len() + uncovered.len(),
+            macro_name,
+            sources.len(),
+            kind,
+            location.display()
+        )
+        .green()
+    );
+    println!();
+
+    print_coverage(&covered, &uncovered, kind);
+}
+
+/// Print uncovered findings first, then covered ones, under "Missing a {kind}" headings
+fn print_coverage(covered: &[&MacroInstance], uncovered: &[&MacroInstance], kind: &str) {
+    if !uncovered.is_empty() {
+        println!(
+            "{}",
+            format!("Missing a {} ({}):", kind, uncovered.len())
+                .red()
+                .bold()
+        );
+        for instance in uncovered {
+            print_labelled(instance);
+        }
+    }
+
+    if !covered.is_empty() {
+        println!(
+            "{}",
+            format!("Referenced by a {} ({}):", kind, covered.len())
+                .green()
+                .bold()
+        );
+        for instance in covered {
+            print_labelled(instance);
+        }
+    }
+}
+
+/// Extract the scaling keyword from a description like "scales with tenants"
+///
+/// Takes the word following the last "with", "by", "per" or "on", falling back
+/// to the final word of the description.
+fn scaling_dimension(description: &str) -> String {
+    let words: Vec<String> = description
+        .split_whitespace()
+        .map(|w| {
+            w.trim_matches(|c: char| !c.is_alphanumeric() && c != '_')
+                .to_lowercase()
+        })
+        .filter(|w| !w.is_empty())
+        .collect();
+
+    let keyword = words
+        .iter()
+        .rposition(|w| matches!(w.as_str(), "with" | "by" | "per" | "on"))
+        .and_then(|idx| words.get(idx + 1))
+        .or_else(|| words.last());
+
+    keyword
+        .cloned()
+        .unwrap_or_else(|| "unspecified".to_string())
+}
+
+/// Group markers by the owner of their item, with a count per macro
+///
+/// Owners come from `owner` markers on the same item, and an item with several
+/// owners counts towards each. Markers on items without one fall back to the
+/// author of their line when blame information is attached (`--blame`), and are
+/// otherwise reported as unowned.
+pub fn owners(instances: &[MacroInstance]) {
+    let owner_markers: Vec<_> = instances
+        .iter()
+        .filter(|i| i.macro_name == "owner")
+        .collect();
+
+    let mut owners: BTreeMap<String, Vec<&MacroInstance>> = BTreeMap::new();
+    let mut unowned = Vec::new();
+    for instance in instances.iter().filter(|i| i.macro_name != "owner") {
+        let mut explicit: Vec<&str> = owner_markers
+            .iter()
+            .filter(|owner| owner.same_item(instance))
+            .filter_map(|owner| owner.argument_value())
+            .collect();
+        explicit.sort_unstable();
+        explicit.dedup();
+
+        if !explicit.is_empty() {
+            for owner in explicit {
+                owners.entry(owner.to_string()).or_default().push(instance);
+            }
+        } else if let Some(blame) = &instance.blame {
+            owners
+                .entry(format!("{} (git blame)", blame.author))
+                .or_default()
+                .push(instance);
+        } else {
+            unowned.push(instance);
+        }
+    }
+
+    if owners.is_empty() && unowned.is_empty() {
+        println!("{}", "No markers found.".yellow());
+        return;
+    }
+
+    println!(
+        "{}",
+        format!(
+            "Found markers for {} owners ({} unowned):",
+            owners.len(),
+            unowned.len()
+        )
+        .green()
+    );
+    println!();
+
+    for (owner, markers) in &owners {
+        print_owner_group(owner.cyan().bold(), markers);
+    }
+    if !unowned.is_empty() {
+        print_owner_group("Unowned".red().bold(), &unowned);
+        if instances.iter().all(|i| i.blame.is_none()) {
+            println!(
+                "{}",
+                "Mark items with #[owner(\"...\")], or pass --blame to attribute them by git author."
+                    .dimmed()
+            );
+        }
+    }
+}
+
+/// Print an owner header with its marker count, then the count of each macro
+fn print_owner_group(header: ColoredString, markers: &[&MacroInstance]) {
+    let mut by_macro: BTreeMap<&str, usize> = BTreeMap::new();
+    for marker in markers {
+        *by_macro.entry(&marker.macro_name).or_default() += 1;
+    }
+
+    println!("{} ({})", header, markers.len());
+    for (macro_name, count) in by_macro {
+        println!("  {:30} : {}", macro_name, count);
+    }
+    println!();
+}
+
+/// Print findings under one header per group, with a count in each header
+///
+/// `group_noun` names what the groups represent (e.g. "scaling dimension") and
+/// `noun` what the findings are, for the summary and empty-state messages.
+fn print_grouped(groups: &BTreeMap<String, Vec<&MacroInstance>>, group_noun: &str, noun: &str) {
+    if groups.is_empty() {
+        println!("{}", format!("No {} found.", noun).yellow());
+        return;
+    }
+
+    let total: usize = groups.values().map(Vec::len).sum();
+    println!(
+        "{}",
+        format!(
+            "Found {} {} across {} {} groups:",
+            total,
+            noun,
+            groups.len(),
+            group_noun
+        )
+        .green()
+    );
+    println!();
+
+    for (group, members) in groups {
+        println!("{}", format!("{} ({})", group, members.len()).cyan().bold());
+        for instance in members {
+            println!(
+                "  {} {}",
+                format!("{}:{}", instance.path.display(), instance.line).blue(),
+                instance.argument_value().unwrap_or_default().yellow()
+            );
+            println!("      {}", instance.context_block("      "));
+        }
+        println!();
+    }
+}
+
+/// List every instance of a single marker, labelled with its argument
+///
+/// `noun` describes what the marker tracks and is used in the heading,
+/// e.g. "items pending feature parity".
+pub fn list_marker(instances: &[MacroInstance], macro_name: &str, noun: &str) {
+    let matching = marked(instances, macro_name);
+
+    if matching.is_empty() {
+        println!("{}", format!("No {} found.", noun).yellow());
+        return;
+    }
+
+    println!("{}", format!("Found {} {}:", matching.len(), noun).green());
+    println!();
+
+    for instance in matching {
+        print_labelled(instance);
+    }
+}
+
+/// Every instance of one marker, in scan order
+fn marked<'a>(instances: &'a [MacroInstance], macro_name: &str) -> Vec<&'a MacroInstance> {
+    instances
+        .iter()
+        .filter(|i| i.macro_name == macro_name)
+        .collect()
+}
+
+/// Print a finding's location followed by its argument and context
+fn print_labelled(instance: &MacroInstance) {
+    println!(
+        "{} {}",
+        format!("{}:{}", instance.path.display(), instance.line).blue(),
+        instance.argument_value().unwrap_or_default().yellow()
+    );
+    println!("    {}", instance.context_block("    "));
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use code_status_scanner::Scanner;
+
+    use super::*;
+
+    fn date(input: &str) -> Date {
+        Date::parse(input).unwrap()
+    }
+
+    fn scan(source: &str) -> Vec<MacroInstance> {
+        let file = Path::new("src/lib.rs");
+        Scanner::new().scan_source(file, file, source)
+    }
+
+    /// `(item, argument)` of each finding
+    fn labelled<'a>(found: &[&'a MacroInstance]) -> Vec<(&'a str, &'a str)> {
+        found
+            .iter()
+            .map(|i| {
+                (
+                    i.item_name.as_deref().unwrap_or_default(),
+                    i.argument_value().unwrap_or_default(),
+                )
+            })
+            .collect()
+    }
+
+    /// Group name with the items in it, in group order
+    fn grouped<'a>(
+        groups: &BTreeMap<String, Vec<&'a MacroInstance>>,
+    ) -> Vec<(String, Vec<&'a str>)> {
+        groups
+            .iter()
+            .map(|(group, found)| {
+                let items = found
+                    .iter()
+                    .map(|i| i.item_name.as_deref().unwrap_or_default())
+                    .collect();
+                (group.clone(), items)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn sunsets_are_ordered_by_date_and_malformed_ones_kept_apart() {
+        let instances = scan(
+            r#"
+#[requires_feature_sunset("2025-06-01")]
+fn later() {}
+
+#[requires_feature_sunset("2025-01-01")]
+fn sooner() {}
+
+#[requires_feature_sunset("next spring")]
+fn someday() {}
+"#,
+        );
+
+        let (scheduled, malformed) = sunset_schedule(&instances);
+        let names: Vec<_> = scheduled
+            .iter()
+            .map(|(date, i)| (date.to_string(), i.item_name.as_deref().unwrap()))
+            .collect();
+        assert_eq!(
+            names,
+            [
+                ("2025-01-01".to_string(), "sooner"),
+                ("2025-06-01".to_string(), "later")
+            ]
+        );
+        assert_eq!(malformed.len(), 1);
+        assert_eq!(malformed[0].item_name.as_deref(), Some("someday"));
+    }
+
+    #[test]
+    fn sunset_is_past_due_from_its_own_day() {
+        let sunset = date("2025-01-01");
+
+        assert!(!is_past_due(sunset, date("2024-12-31")));
+        assert!(is_past_due(sunset, date("2025-01-01")));
+        assert!(is_past_due(sunset, date("2025-01-02")));
+    }
+
+    #[test]
+    fn parity_lists_each_pending_item() {
+        let instances = scan(
+            r#"
+#[requires_feature_parity("matches python impl")]
+fn tokenize() {}
+
+#[needs("docs")]
+#[requires_feature_parity("matches python error messages")]
+fn report_error() {}
+"#,
+        );
+
+        assert_eq!(
+            labelled(&marked(&instances, "requires_feature_parity")),
+            [
+                ("tokenize", "matches python impl"),
+                ("report_error", "matches python error messages")
+            ]
+        );
+    }
+
+    #[test]
+    fn compat_surface_lists_every_shim() {
+        let instances = scan(
+            r#"
+#[legacy_compat("pre-2.0 clients")]
+fn export_v1() {}
+
+#[legacy_compat("xml payloads")]
+struct XmlCodec;
+"#,
+        );
+
+        let shims = compat_shims(&instances);
+        let listed: Vec<_> = shims.iter().map(|s| s.shim).collect();
+        assert_eq!(
+            labelled(&listed),
+            [
+                ("export_v1", "pre-2.0 clients"),
+                ("XmlCodec", "xml payloads")
+            ]
+        );
+        assert!(shims.iter().all(|s| !s.has_removal_timing()));
+    }
+
+    #[test]
+    fn compat_surface_takes_removal_timing_from_the_same_item() {
+        let instances = scan(
+            r#"
+#[legacy_compat("pre-2.0 clients")]
+#[deprecated_since("1.4.0")]
+#[requires_feature_sunset("2030-01-01")]
+fn export_v1() {}
+
+#[legacy_compat("xml payloads")]
+#[deprecated_since("1.6.0")]
+fn decode_xml() {}
+
+#[legacy_compat("old config keys")]
+fn read_config() {}
+
+#[deprecated_since("1.2.0")]
+fn unrelated() {}
+"#,
+        );
+
+        let timing: Vec<_> = compat_shims(&instances)
+            .iter()
+            .map(|s| {
+                (
+                    s.shim.item_name.as_deref().unwrap(),
+                    s.deprecated_since,
+                    s.sunset.map(|d| d.to_string()),
+                )
+            })
+            .collect();
+        assert_eq!(
+            timing,
+            [
+                ("export_v1", Some("1.4.0"), Some("2030-01-01".to_string())),
+                ("decode_xml", Some("1.6.0"), None),
+                ("read_config", None, None)
+            ]
+        );
+    }
+
+    #[test]
+    fn capacity_groups_by_scaling_dimension() {
+        let instances = scan(
+            r#"
+#[requires_capacity_planning("scales with tenants")]
+fn provision() {}
+
+#[requires_capacity_planning("memory grows per Tenant.")]
+fn cache_settings() {}
+
+#[requires_capacity_planning("one row by request")]
+fn audit() {}
+
+#[requires_capacity_planning("")]
+fn reserve() {}
+"#,
+        );
+
+        assert_eq!(
+            grouped(&capacity_groups(&instances)),
+            [
+                ("request".to_string(), vec!["audit"]),
+                ("tenant".to_string(), vec!["cache_settings"]),
+                ("tenants".to_string(), vec!["provision"]),
+                ("unspecified".to_string(), vec!["reserve"])
+            ]
+        );
+    }
+
+    #[test]
+    fn scaling_dimension_falls_back_to_the_last_word() {
+        assert_eq!(scaling_dimension("scales with Users"), "users");
+        assert_eq!(scaling_dimension("grows per day, then by region"), "region");
+        assert_eq!(scaling_dimension("connection count"), "count");
+        assert_eq!(scaling_dimension("  "), "unspecified");
+    }
+
+    /// `(covered, uncovered)` item names
+    fn coverage_names<'a>(
+        (covered, uncovered): (Vec<&'a MacroInstance>, Vec<&'a MacroInstance>),
+    ) -> (Vec<&'a str>, Vec<&'a str>) {
+        let names = |found: Vec<&'a MacroInstance>| {
+            found
+                .into_iter()
+                .map(|i| i.item_name.as_deref().unwrap_or_default())
+                .collect()
+        };
+        (names(covered), names(uncovered))
+    }
+
+    #[test]
+    fn fuzz_gaps_match_item_names_in_fuzz_targets() {
+        let fuzz_dir = tempfile::tempdir().unwrap();
+        let targets = fuzz_dir.path().join("fuzz_targets");
+        fs::create_dir(&targets).unwrap();
+        fs::write(
+            targets.join("parse.rs"),
+            "fuzz_target!(|data: &[u8]| { let _ = parse_header(data); });\n",
+        )
+        .unwrap();
+        fs::write(targets.join("notes.txt"), "decode_body is next").unwrap();
+
+        let instances = scan(
+            r#"
+#[requires_fuzzing("untrusted headers")]
+fn parse_header(data: &[u8]) {}
+
+#[requires_fuzzing("untrusted bodies")]
+fn decode_body(data: &[u8]) {}
+
+#[requires_fuzzing("headers again")]
+fn parse_header_v2(data: &[u8]) {}
+"#,
+        );
+
+        let sources = read_rust_sources(fuzz_dir.path());
+        assert_eq!(
+            coverage_names(reference_coverage(&instances, "requires_fuzzing", &sources)),
+            (vec!["parse_header"], vec!["decode_body", "parse_header_v2"])
+        );
+    }
+
+    #[test]
+    fn replacement_pairs_show_the_removal_marker_of_the_old_item() {
+        let instances = scan(
+            r#"
+#[temporary("until v2 ships")]
+fn old_parse() {}
+
+#[deprecated_since("1.4.0")]
+fn old_render() {}
+
+fn old_save() {}
+
+#[replaces("old_parse")]
+fn parse() {}
+
+#[replaces("old_render")]
+fn render() {}
+
+#[replaces("old_save")]
+fn save() {}
+"#,
+        );
+
+        let pairs: Vec<_> = replacements(&instances)
+            .into_iter()
+            .map(|(pair, removal)| {
+                (
+                    pair.item_name.as_deref().unwrap(),
+                    pair.argument_value().unwrap(),
+                    removal.map(|marker| marker.macro_name.as_str()),
+                )
+            })
+            .collect();
+        assert_eq!(
+            pairs,
+            [
+                ("parse", "old_parse", Some("temporary")),
+                ("render", "old_render", Some("deprecated_since")),
+                ("save", "old_save", None)
+            ]
+        );
+    }
+
+    #[test]
+    fn i18n_lists_locale_sensitive_items() {
+        let instances = scan(
+            r#"
+#[requires_locale("date formatting")]
+fn format_date() {}
+
+#[needs("docs")]
+fn helper() {}
+
+impl Invoice {
+    #[requires_locale("currency symbols")]
+    fn total(&self) -> String {}
+}
+"#,
+        );
+
+        assert_eq!(
+            labelled(&marked(&instances, "requires_locale")),
+            [
+                ("format_date", "date formatting"),
+                ("total", "currency symbols")
+            ]
+        );
+    }
+
+    #[test]
+    fn error_handling_groups_markers_per_function() {
+        let instances = scan(
+            r#"
+#[panic_path]
+#[requires_cleanup_on_error("release the lock")]
+fn flush() {}
+
+#[includes_unwrap]
+#[requires_cleanup_on_error("delete the temp file")]
+fn write_temp() {}
+
+#[panic_path]
+fn parse() {}
+
+#[untested]
+fn helper() {}
+"#,
+        );
+
+        let functions: Vec<_> = error_paths(&instances)
+            .into_iter()
+            .map(|((_, item), markers)| {
+                let names: Vec<_> = markers.iter().map(|m| m.macro_name.as_str()).collect();
+                (item, names, is_high_risk(&markers))
+            })
+            .collect();
+        assert_eq!(
+            functions,
+            [
+                (
+                    "flush".to_string(),
+                    vec!["panic_path", "requires_cleanup_on_error"],
+                    true
+                ),
+                ("parse".to_string(), vec!["panic_path"], false),
+                (
+                    "write_temp".to_string(),
+                    vec!["includes_unwrap", "requires_cleanup_on_error"],
+                    false
+                )
+            ]
+        );
+    }
+
+    #[test]
+    fn numerics_lists_approximations() {
+        let instances = scan(
+            r#"
+#[approximation("fast inverse square root, 0.2% error")]
+fn inv_sqrt(x: f32) -> f32 {}
+
+#[perf_critical]
+#[approximation("Stirling's formula above n = 20")]
+fn log_factorial(n: u64) -> f64 {}
+"#,
+        );
+
+        assert_eq!(
+            labelled(&marked(&instances, "approximation")),
+            [
+                ("inv_sqrt", "fast inverse square root, 0.2% error"),
+                ("log_factorial", "Stirling's formula above n = 20")
+            ]
+        );
+    }
+
+    #[test]
+    fn concurrency_groups_hazards_by_type() {
+        let instances = scan(
+            r#"
+#[deadlock_risk("takes both locks")]
+fn transfer() {}
+
+#[data_race_risk("unsynchronized counter")]
+struct Hits;
+
+#[requires_ordering("publish before setting ready")]
+fn publish() {}
+
+#[requires_ordering("lock accounts before the ledger")]
+fn settle() {}
+
+#[not_thread_safe("uses Rc")]
+struct Cache;
+
+#[blocking_io("reads the config file")]
+async fn load() {}
+
+#[perf_critical]
+fn hot() {}
+"#,
+        );
+
+        assert_eq!(
+            grouped(&concurrency_groups(&instances)),
+            [
+                ("blocking_io".to_string(), vec!["load"]),
+                ("data_race_risk".to_string(), vec!["Hits"]),
+                ("deadlock_risk".to_string(), vec!["transfer"]),
+                ("not_thread_safe".to_string(), vec!["Cache"]),
+                ("requires_ordering".to_string(), vec!["publish", "settle"])
+            ]
+        );
+    }
+
+    const AUTHZ_SOURCE: &str = r#"
+#[security_sensitive]
+#[requires_permission_check("admin")]
+fn delete_user() {}
+
+#[requires_permission_check("admin")]
+fn reset_password() {}
+
+#[requires_permission_check("billing:read")]
+fn invoices() {}
+
+#[security_sensitive]
+fn export_keys() {}
+"#;
+
+    #[test]
+    fn authz_groups_checkpoints_by_permission() {
+        let instances = scan(AUTHZ_SOURCE);
+
+        assert_eq!(
+            grouped(&permission_groups(&instances)),
+            [
+                ("admin".to_string(), vec!["delete_user", "reset_password"]),
+                ("billing:read".to_string(), vec!["invoices"])
+            ]
+        );
+    }
+
+    #[test]
+    fn authz_flags_sensitive_items_without_a_check() {
+        let instances = scan(AUTHZ_SOURCE);
+
+        assert_eq!(labelled(&unguarded(&instances)), [("export_keys", "")]);
+    }
+
+    #[test]
+    fn compat_test_gaps_match_item_names_in_tests() {
+        let tests_dir = tempfile::tempdir().unwrap();
+        fs::write(
+            tests_dir.path().join("v1_clients.rs"),
+            "#[test]\nfn v1_payload_still_decodes() { assert!(decode_v1(b\"{}\").is_ok()); }\n",
+        )
+        .unwrap();
+
+        let instances = scan(
+            r#"
+#[requires_backward_compat_test("v1 clients")]
+fn decode_v1(bytes: &[u8]) {}
+
+#[requires_backward_compat_test("v1 clients")]
+fn encode_v1(value: &Value) {}
+"#,
+        );
+
+        let sources = read_rust_sources(tests_dir.path());
+        assert_eq!(
+            coverage_names(reference_coverage(
+                &instances,
+                "requires_backward_compat_test",
+                &sources
+            )),
+            (vec!["decode_v1"], vec!["encode_v1"])
+        );
+    }
+
+    #[test]
+    fn secrets_are_inventoried_once_each() {
+        let instances = scan(
+            r#"
+#[requires_secret("STRIPE_KEY")]
+#[security_sensitive]
+fn charge() {}
+
+#[requires_secret(" STRIPE_KEY ")]
+fn refund() {}
+
+#[requires_secret("DATABASE_URL")]
+fn connect() {}
+"#,
+        );
+
+        let inventory = secret_inventory(&instances);
+        let sites: Vec<_> = inventory
+            .iter()
+            .map(|(name, sites)| {
+                let sites: Vec<_> = sites
+                    .iter()
+                    .map(|site| {
+                        (
+                            site.item_name.as_deref().unwrap(),
+                            is_security_sensitive(&instances, site),
+                        )
+                    })
+                    .collect();
+                (*name, sites)
+            })
+            .collect();
+        assert_eq!(
+            sites,
+            [
+                ("DATABASE_URL", vec![("connect", false)]),
+                ("STRIPE_KEY", vec![("charge", true), ("refund", false)])
+            ]
+        );
+    }
+
+    #[test]
+    fn review_gate_passes_before_the_deadline() {
+        let instances = scan(
+            r#"
+#[requires_review_before("2025-03-01")]
+fn rotate_keys() {}
+"#,
+        );
+
+        assert!(overdue_reviews(&instances, date("2025-02-28")).is_empty());
+        assert!(review_gate(&instances, date("2025-02-28")));
+    }
+
+    #[test]
+    fn review_gate_fails_once_a_deadline_passes() {
+        let instances = scan(
+            r#"
+#[requires_review_before("2025-03-01")]
+fn rotate_keys() {}
+
+#[requires_review_before("2025-01-15")]
+fn session_store() {}
+
+#[requires_review_before("2026-01-01")]
+fn audit_log() {}
+"#,
+        );
+
+        let overdue: Vec<_> = overdue_reviews(&instances, date("2025-03-01"))
+            .into_iter()
+            .map(|(deadline, i)| (deadline.to_string(), i.item_name.as_deref().unwrap()))
+            .collect();
+        assert_eq!(
+            overdue,
+            [
+                ("2025-01-15".to_string(), "session_store"),
+                ("2025-03-01".to_string(), "rotate_keys")
+            ]
+        );
+        assert!(!review_gate(&instances, date("2025-03-01")));
+    }
+
+    #[test]
+    fn precision_groups_decisions_by_numeric_type() {
+        let instances = scan(
+            r#"
+#[numeric_precision("f32 sufficient for screen coordinates")]
+fn zoom() {}
+
+#[numeric_precision("Decimal, never f64, for money")]
+fn total() {}
+
+#[numeric_precision("F64 to keep error below 1e-9")]
+fn integrate() {}
+
+#[numeric_precision("16.16 fixed-point on the DSP")]
+fn mix() {}
+
+#[numeric_precision("whatever the sensor reports")]
+fn sample() {}
+"#,
+        );
+
+        assert_eq!(
+            grouped(&precision_groups(&instances)),
+            [
+                ("decimal".to_string(), vec!["total"]),
+                ("f32".to_string(), vec!["zoom"]),
+                ("f64".to_string(), vec!["integrate"]),
+                ("fixed-point".to_string(), vec!["mix"]),
+                ("other".to_string(), vec!["sample"])
+            ]
+        );
+    }
+
+    #[test]
+    fn shutdown_lists_components_needing_graceful_shutdown() {
+        let instances = scan(
+            r#"
+#[requires_graceful_shutdown("drain in-flight requests")]
+struct HttpServer;
+
+#[requires_graceful_shutdown("flush buffered events")]
+fn run_event_writer() {}
+
+#[requires_circuit_breaker("payments api")]
+fn charge() {}
+"#,
+        );
+
+        assert_eq!(
+            labelled(&marked(&instances, "requires_graceful_shutdown")),
+            [
+                ("HttpServer", "drain in-flight requests"),
+                ("run_event_writer", "flush buffered events")
+            ]
+        );
+    }
+
+    const RESILIENCE_SOURCE: &str = r#"
+#[dependency_sensitive]
+#[requires_circuit_breaker("payments api")]
+fn charge() {}
+
+#[requires_circuit_breaker(" payments api ")]
+fn refund() {}
+
+#[requires_circuit_breaker("geocoder")]
+fn locate() {}
+
+#[dependency_sensitive]
+fn fetch_rates() {}
+"#;
+
+    #[test]
+    fn resilience_groups_breakers_by_dependency() {
+        let instances = scan(RESILIENCE_SOURCE);
+
+        assert_eq!(
+            grouped(&breaker_groups(&instances)),
+            [
+                ("geocoder".to_string(), vec!["locate"]),
+                ("payments api".to_string(), vec!["charge", "refund"])
+            ]
+        );
+    }
+
+    #[test]
+    fn resilience_flags_dependency_sensitive_items_without_a_breaker() {
+        let instances = scan(RESILIENCE_SOURCE);
+
+        assert_eq!(labelled(&unprotected(&instances)), [("fetch_rates", "")]);
+    }
+
+    #[test]
+    fn load_test_gate_groups_paths_by_milestone() {
+        let instances = scan(
+            r#"
+#[requires_load_test("before GA")]
+fn checkout() {}
+
+#[requires_load_test("before beta")]
+fn search() {}
+
+#[requires_load_test("before GA ")]
+fn login() {}
+"#,
+        );
+
+        assert_eq!(
+            grouped(&milestone_groups(&instances)),
+            [
+                ("before GA".to_string(), vec!["checkout", "login"]),
+                ("before beta".to_string(), vec!["search"])
+            ]
+        );
+    }
+
+    #[test]
+    fn a11y_lists_ui_needing_accessibility_review() {
+        let instances = scan(
+            r#"
+#[requires_accessibility_review("color-only error states")]
+fn render_form() {}
+
+#[requires_accessibility_review("focus trap in modal")]
+struct Modal;
+
+#[requires_locale("date formatting")]
+fn render_date() {}
+"#,
+        );
+
+        assert_eq!(
+            labelled(&marked(&instances, "requires_accessibility_review")),
+            [
+                ("render_form", "color-only error states"),
+                ("Modal", "focus trap in modal")
+            ]
+        );
+    }
+
+    #[test]
+    fn chaos_groups_scenarios_ignoring_case_and_spacing() {
+        let instances = scan(
+            r#"
+#[requires_chaos_test("network partition")]
+fn replicate() {}
+
+#[requires_chaos_test("Network   Partition")]
+fn elect_leader() {}
+
+#[requires_chaos_test(" node failure")]
+fn rebalance() {}
+"#,
+        );
+
+        assert_eq!(
+            grouped(&scenario_groups(&instances)),
+            [
+                (
+                    "network partition".to_string(),
+                    vec!["replicate", "elect_leader"]
+                ),
+                ("node failure".to_string(), vec!["rebalance"])
+            ]
+        );
+    }
+
+    #[test]
+    fn docs_review_lists_items_needing_documentation_review() {
+        let instances = scan(
+            r#"
+#[requires_documentation_review("new config keys")]
+pub struct Settings;
+
+#[requires_documentation_review("changed retry semantics")]
+pub fn retry() {}
+
+#[requires_feature_documentation("PROJ-42")]
+pub fn export() {}
+"#,
+        );
+
+        assert_eq!(
+            labelled(&marked(&instances, "requires_documentation_review")),
+            [
+                ("Settings", "new config keys"),
+                ("retry", "changed retry semantics")
+            ]
+        );
+    }
+
+    const OBSERVABILITY_SOURCE: &str = r#"
+#[perf_critical]
+#[requires_metrics("p99 latency histogram")]
+fn route() {}
+
+#[perf_critical]
+#[observed("requests_total counter")]
+fn handle() {}
+
+#[perf_critical]
+fn serialize() {}
+
+#[requires_metrics("queue depth gauge")]
+fn enqueue() {}
+
+#[observed("cache hit ratio")]
+fn lookup() {}
+"#;
+
+    #[test]
+    fn observability_lists_metrics_requirements() {
+        let instances = scan(OBSERVABILITY_SOURCE);
+
+        assert_eq!(
+            labelled(&marked(&instances, "requires_metrics")),
+            [
+                ("route", "p99 latency histogram"),
+                ("enqueue", "queue depth gauge")
+            ]
+        );
+    }
+
+    #[test]
+    fn observability_flags_perf_critical_paths_with_neither_marker() {
+        let instances = scan(OBSERVABILITY_SOURCE);
+
+        assert_eq!(labelled(&unobserved(&instances)), [("serialize", "")]);
+    }
+
+    #[test]
+    fn injection_surface_groups_by_canonical_class() {
+        let instances = scan(
+            r#"
+#[requires_input_sanitization("SQL")]
+fn find_user() {}
+
+#[requires_input_sanitization("sql")]
+fn search_orders() {}
+
+#[requires_input_sanitization(" html ")]
+fn greeting() {}
+
+#[requires_input_sanitization("shell")]
+fn run_hook() {}
+"#,
+        );
+
+        assert_eq!(
+            grouped(&injection_groups(&instances)),
+            [
+                ("HTML".to_string(), vec!["greeting"]),
+                ("SQL".to_string(), vec!["find_user", "search_orders"]),
+                ("shell".to_string(), vec!["run_hook"])
+            ]
+        );
+    }
+
+    #[test]
+    fn release_notes_gaps_match_names_and_notes_in_the_changelog() {
+        let dir = tempfile::tempdir().unwrap();
+        let changelog = dir.path().join("CHANGELOG.md");
+        fs::write(
+            &changelog,
+            "# Changelog\n\n## [Unreleased]\n\n### Added\n\
+             - `export_csv` for spreadsheet users\n\
+             - Bulk invites (proj-42)\n",
+        )
+        .unwrap();
+
+        let instances = scan(
+            r#"
+#[requires_feature_documentation("spreadsheet export")]
+pub fn export_csv() {}
+
+#[requires_feature_documentation("PROJ-42")]
+pub fn invite_many() {}
+
+#[requires_feature_documentation("dark mode")]
+pub fn theme() {}
+
+#[requires_feature_documentation("")]
+pub fn export() {}
+"#,
+        );
+
+        let text = fs::read_to_string(&changelog).unwrap();
+        assert_eq!(
+            coverage_names(release_note_coverage(&instances, &text)),
+            (vec!["export_csv", "invite_many"], vec!["theme", "export"])
+        );
+    }
+
+    #[test]
+    fn property_test_gaps_only_count_property_tests() {
+        let tests_dir = tempfile::tempdir().unwrap();
+        fs::write(
+            tests_dir.path().join("roundtrip.rs"),
+            "proptest! {\n    #[test]\n    fn roundtrips(s in \".*\") {\n        \
+             prop_assert_eq!(decode(&encode(&s)), s);\n    }\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            tests_dir.path().join("ordering.rs"),
+            "#[quickcheck]\nfn sorted(xs: Vec<u8>) -> bool { is_sorted(&sort(xs)) }\n",
+        )
+        .unwrap();
+        fs::write(
+            tests_dir.path().join("unit.rs"),
+            "#[test]\nfn parses() { assert!(parse(\"1\").is_ok()); }\n",
+        )
+        .unwrap();
+
+        let instances = scan(
+            r#"
+#[requires_property_test("roundtrip")]
+fn encode(s: &str) {}
+
+#[requires_property_test("output is sorted")]
+fn sort(xs: Vec<u8>) {}
+
+#[requires_property_test("accepts what it prints")]
+fn parse(s: &str) {}
+"#,
+        );
+
+        let sources = read_property_tests(tests_dir.path());
+        assert_eq!(sources.len(), 2);
+        assert_eq!(
+            coverage_names(reference_coverage(
+                &instances,
+                "requires_property_test",
+                &sources
+            )),
+            (vec!["encode", "sort"], vec!["parse"])
+        );
+    }
+}
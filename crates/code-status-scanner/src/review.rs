@@ -0,0 +1,158 @@
+//! Open/closed tracking of concern markers via `#[reviewed("<date> by <who>")]`.
+
+use crate::{date::Date, MacroInstance};
+
+/// Markers that record an outcome rather than raise a concern
+const RESOLUTION_MARKERS: &[&str] = &["reviewed", "migration_complete"];
+
+/// A parsed `reviewed` argument
+#[derive(Debug, PartialEq, Eq)]
+pub struct Review<'a> {
+    pub date: Date,
+    pub reviewer: &'a str,
+}
+
+/// Parse `"<YYYY-MM-DD> by <reviewer>"`
+pub fn parse(argument: &str) -> Option<Review<'_>> {
+    let (date, reviewer) = argument.trim().split_once(" by ")?;
+    let reviewer = reviewer.trim();
+    if reviewer.is_empty() {
+        return None;
+    }
+
+    Some(Review {
+        date: Date::parse(date.trim())?,
+        reviewer,
+    })
+}
+
+/// Whether a concern is closed by a well-formed `reviewed` on the same item
+///
+/// With blame attached, the review must be dated on or after the commit that
+/// last touched the concern's line, so a concern raised again after its review
+/// is open. Without blame, concern markers carry no date and any valid review
+/// closes them.
+fn is_closed(concern: &MacroInstance, instances: &[MacroInstance]) -> bool {
+    instances.iter().any(|i| {
+        i.macro_name == "reviewed"
+            && i.same_item(concern)
+            && i.argument_value().and_then(parse).is_some_and(|review| {
+                concern
+                    .blame
+                    .as_ref()
+                    .is_none_or(|blame| review.date >= blame.commit_date)
+            })
+    })
+}
+
+/// Keep only open concerns, dropping closed ones and the resolution markers themselves
+pub fn retain_open(instances: &mut Vec<MacroInstance>) {
+    let open: Vec<bool> = instances
+        .iter()
+        .map(|i| !RESOLUTION_MARKERS.contains(&i.macro_name.as_str()) && !is_closed(i, instances))
+        .collect();
+
+    let mut open = open.into_iter();
+    instances.retain(|_| open.next().unwrap_or(false));
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use code_status_scanner::{blame::BlameInfo, Scanner};
+
+    use super::*;
+
+    fn scan(source: &str) -> Vec<MacroInstance> {
+        let file = Path::new("src/lib.rs");
+        Scanner::new().scan_source(file, file, source)
+    }
+
+    fn open_items(mut instances: Vec<MacroInstance>) -> Vec<String> {
+        retain_open(&mut instances);
+        instances
+            .into_iter()
+            .map(|i| format!("{} {}", i.macro_name, i.item_name.unwrap_or_default()))
+            .collect()
+    }
+
+    #[test]
+    fn parses_date_and_reviewer() {
+        assert_eq!(
+            parse(" 2024-03-01 by alice "),
+            Some(Review {
+                date: Date::parse("2024-03-01").unwrap(),
+                reviewer: "alice",
+            })
+        );
+        assert_eq!(
+            parse("2024-03-01 by Alice Smith").map(|r| r.reviewer),
+            Some("Alice Smith")
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_reviews() {
+        for argument in [
+            "2024-03-01",
+            "2024-03-01 by ",
+            "alice",
+            "by alice",
+            "2024-02-30 by alice",
+            "March 1st by alice",
+        ] {
+            assert_eq!(parse(argument), None, "{:?}", argument);
+        }
+    }
+
+    #[test]
+    fn reviewed_concerns_are_closed_and_others_open() {
+        let instances = scan(
+            r#"
+#[security_sensitive]
+#[reviewed("2024-03-01 by alice")]
+fn login() {}
+
+#[security_sensitive]
+fn logout() {}
+
+#[security_sensitive]
+#[reviewed("last week by bob")]
+fn refresh() {}
+"#,
+        );
+
+        assert_eq!(
+            open_items(instances),
+            ["security_sensitive logout", "security_sensitive refresh"]
+        );
+    }
+
+    #[test]
+    fn review_older_than_the_blamed_concern_leaves_it_open() {
+        let mut instances = scan(
+            r#"
+#[security_sensitive]
+#[reviewed("2024-03-01 by alice")]
+fn login() {}
+
+#[security_sensitive]
+#[reviewed("2024-03-01 by alice")]
+fn logout() {}
+"#,
+        );
+        for (instance, date) in instances
+            .iter_mut()
+            .filter(|i| i.macro_name == "security_sensitive")
+            .zip(["2024-02-01", "2024-04-01"])
+        {
+            instance.blame = Some(BlameInfo {
+                author: "carol".to_string(),
+                commit_date: Date::parse(date).unwrap(),
+            });
+        }
+
+        assert_eq!(open_items(instances), ["security_sensitive logout"]);
+    }
+}
@@ -0,0 +1,209 @@
+//! SARIF 2.1.0 output for `--format sarif`, as consumed by GitHub code scanning.
+//!
+//! Every scanned macro is a rule whose ID is the macro name, and each finding
+//! carries its finding ID as a fingerprint, so GitHub can track a result
+//! across runs even as the code around it moves.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::{explain, severity::Severities, MacroInstance, Severity};
+
+const SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// A SARIF result level
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum Level {
+    Error,
+    Warning,
+    Note,
+}
+
+impl Level {
+    /// The default level for findings of a severity tier
+    pub fn of(severity: Severity) -> Self {
+        match severity {
+            Severity::Critical => Self::Error,
+            Severity::Warning => Self::Warning,
+            Severity::Info => Self::Note,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Log<'a> {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: [Run<'a>; 1],
+}
+
+#[derive(Serialize)]
+struct Run<'a> {
+    tool: Tool<'a>,
+    results: Vec<SarifResult<'a>>,
+}
+
+#[derive(Serialize)]
+struct Tool<'a> {
+    driver: Driver<'a>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Driver<'a> {
+    name: &'static str,
+    version: &'static str,
+    information_uri: &'static str,
+    rules: Vec<Rule<'a>>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Rule<'a> {
+    id: &'a str,
+    short_description: Message,
+    default_configuration: Configuration,
+}
+
+#[derive(Serialize)]
+struct Configuration {
+    level: Level,
+}
+
+#[derive(Serialize)]
+struct Message {
+    text: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult<'a> {
+    rule_id: &'a str,
+    rule_index: usize,
+    level: Level,
+    message: Message,
+    locations: [Location; 1],
+    partial_fingerprints: Fingerprints<'a>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Location {
+    physical_location: PhysicalLocation,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PhysicalLocation {
+    artifact_location: ArtifactLocation,
+    region: Region,
+}
+
+#[derive(Serialize)]
+struct ArtifactLocation {
+    uri: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Region {
+    start_line: usize,
+}
+
+#[derive(Serialize)]
+struct Fingerprints<'a> {
+    #[serde(rename = "codeStatusFindingId/v1")]
+    finding_id: &'a str,
+}
+
+/// Print findings as a SARIF log
+///
+/// Levels follow each macro's severity, unless `overrides` sets one for the
+/// rule and its results.
+pub fn print<'a>(
+    instances: impl IntoIterator<Item = &'a MacroInstance>,
+    macro_names: &'a [String],
+    severities: &Severities,
+    overrides: &[(String, Level)],
+) {
+    let overrides: HashMap<&str, Level> = overrides
+        .iter()
+        .map(|(name, level)| (name.as_str(), *level))
+        .collect();
+    let level = |name: &str| {
+        overrides
+            .get(name)
+            .copied()
+            .unwrap_or_else(|| Level::of(severities.of(name)))
+    };
+
+    let rules = macro_names
+        .iter()
+        .map(|name| Rule {
+            id: name,
+            short_description: Message {
+                text: explain::explanation(name)
+                    .map_or_else(|| format!("#[{}] marker", name), str::to_string),
+            },
+            default_configuration: Configuration { level: level(name) },
+        })
+        .collect();
+
+    let results = instances
+        .into_iter()
+        .map(|instance| {
+            let name = instance.macro_name.as_str();
+            let text = match instance.argument_value().filter(|arg| !arg.is_empty()) {
+                Some(argument) => argument.to_string(),
+                None => format!("#[{}]", name),
+            };
+            SarifResult {
+                rule_id: name,
+                rule_index: macro_names
+                    .iter()
+                    .position(|known| known == name)
+                    .unwrap_or_default(),
+                level: level(name),
+                message: Message { text },
+                locations: [Location {
+                    physical_location: PhysicalLocation {
+                        artifact_location: ArtifactLocation { uri: uri(instance) },
+                        region: Region {
+                            start_line: instance.line,
+                        },
+                    },
+                }],
+                partial_fingerprints: Fingerprints {
+                    finding_id: &instance.id,
+                },
+            }
+        })
+        .collect();
+
+    crate::json::print(&Log {
+        schema: SCHEMA,
+        version: "2.1.0",
+        runs: [Run {
+            tool: Tool {
+                driver: Driver {
+                    name: env!("CARGO_PKG_NAME"),
+                    version: env!("CARGO_PKG_VERSION"),
+                    information_uri: env!("CARGO_PKG_REPOSITORY"),
+                    rules,
+                },
+            },
+            results,
+        }],
+    });
+}
+
+/// The finding's path as a relative URI with forward slashes
+///
+/// GitHub resolves it against the repository root, so scan from there.
+fn uri(instance: &MacroInstance) -> String {
+    let path = instance.path.display().to_string().replace('\\', "/");
+    path.trim_start_matches("./").replace(' ', "%20")
+}
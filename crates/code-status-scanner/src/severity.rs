@@ -0,0 +1,38 @@
+//! Severity tiers for triaging findings.
+//!
+//! The default tier of each macro comes from `code_status_core::Severity`;
+//! a project's `code-status.toml` can re-rank individual macros.
+
+use std::collections::HashMap;
+
+pub use code_status_core::Severity;
+use colored::{ColoredString, Colorize};
+
+/// The severity of every macro, after the project's overrides
+#[derive(Debug, Default)]
+pub struct Severities {
+    overrides: HashMap<String, Severity>,
+}
+
+impl Severities {
+    pub fn new(overrides: HashMap<String, Severity>) -> Self {
+        Self { overrides }
+    }
+
+    /// The severity of a macro, overridden or default
+    pub fn of(&self, macro_name: &str) -> Severity {
+        self.overrides
+            .get(macro_name)
+            .copied()
+            .unwrap_or_else(|| Severity::of(macro_name))
+    }
+}
+
+/// Render text in the display color of a tier
+pub fn colored(severity: Severity, text: &str) -> ColoredString {
+    match severity {
+        Severity::Critical => text.red().bold(),
+        Severity::Warning => text.yellow().bold(),
+        Severity::Info => text.cyan().bold(),
+    }
+}
@@ -0,0 +1,162 @@
+//! Deadlines of `revisit_in` markers, checked by the `stale` subcommand.
+
+use std::fmt;
+
+use colored::Colorize;
+
+use crate::{date::Date, MacroInstance};
+
+/// A `major.minor.patch` version, with missing components counting as zero
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Version {
+    major: u64,
+    minor: u64,
+    patch: u64,
+}
+
+impl Version {
+    /// Parse `2`, `v2.0` or `2.1.3`, ignoring any `-pre` or `+build` suffix
+    pub fn parse(input: &str) -> Option<Self> {
+        let input = input.trim();
+        let input = input
+            .strip_prefix(['v', 'V'])
+            .unwrap_or(input)
+            .split(['-', '+'])
+            .next()?;
+
+        let mut parts = [0; 3];
+        for (index, part) in input.split('.').enumerate() {
+            *parts.get_mut(index)? = part.parse().ok()?;
+        }
+
+        let [major, minor, patch] = parts;
+        Some(Self {
+            major,
+            minor,
+            patch,
+        })
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// When a `revisit_in` marker comes due
+#[derive(Debug, Clone, Copy)]
+enum Deadline {
+    Date(Date),
+    Version(Version),
+}
+
+impl Deadline {
+    /// An ISO date or a version
+    ///
+    /// A date-shaped argument such as `2024-13-01` is never read as version
+    /// `2024` with a pre-release suffix.
+    fn parse(argument: &str) -> Option<Self> {
+        let parts: Vec<&str> = argument.trim().split('-').collect();
+        let date_shaped =
+            parts.len() == 3 && parts.iter().all(|p| p.chars().all(|c| c.is_ascii_digit()));
+        if date_shaped {
+            Date::parse(argument).map(Self::Date)
+        } else {
+            Version::parse(argument).map(Self::Version)
+        }
+    }
+}
+
+impl fmt::Display for Deadline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Date(date) => write!(f, "{}", date),
+            Self::Version(version) => write!(f, "v{}", version),
+        }
+    }
+}
+
+/// Report `revisit_in` markers whose date or version has been reached
+///
+/// Dates are compared with `today` and versions with `current_version`;
+/// without one, version deadlines are counted but not checked. Arguments that
+/// are neither are listed as malformed. Returns false when any marker is overdue.
+pub fn report(instances: &[MacroInstance], today: Date, current_version: Option<Version>) -> bool {
+    let mut overdue = Vec::new();
+    let mut malformed = Vec::new();
+    let mut unchecked = 0;
+
+    for instance in instances.iter().filter(|i| i.macro_name == "revisit_in") {
+        match instance.argument_value().and_then(Deadline::parse) {
+            Some(deadline @ Deadline::Date(date)) if date <= today => {
+                overdue.push((deadline, instance));
+            },
+            Some(deadline @ Deadline::Version(version)) => match current_version {
+                Some(current) if version <= current => overdue.push((deadline, instance)),
+                Some(_) => {},
+                None => unchecked += 1,
+            },
+            Some(Deadline::Date(_)) => {},
+            None => malformed.push(instance),
+        }
+    }
+
+    if overdue.is_empty() {
+        println!(
+            "{}",
+            format!("No revisit_in deadlines have passed as of {}.", today).green()
+        );
+        println!();
+    } else {
+        println!(
+            "{}",
+            format!("{} revisit_in markers are overdue:", overdue.len())
+                .red()
+                .bold()
+        );
+        println!();
+        for (deadline, instance) in &overdue {
+            println!(
+                "{} {}",
+                format!("due {}", deadline).red(),
+                format!("{}:{}", instance.path.display(), instance.line).blue()
+            );
+            println!("    {}", instance.context_block("    "));
+            println!();
+        }
+    }
+
+    if !malformed.is_empty() {
+        println!(
+            "{}",
+            format!(
+                "{} revisit_in markers are neither a version nor a YYYY-MM-DD date:",
+                malformed.len()
+            )
+            .yellow()
+            .bold()
+        );
+        for instance in &malformed {
+            println!(
+                "{} {}",
+                format!("{}:{}", instance.path.display(), instance.line).blue(),
+                instance.argument_value().unwrap_or_default().yellow()
+            );
+        }
+        println!();
+    }
+
+    if unchecked > 0 {
+        println!(
+            "{}",
+            format!(
+                "{} version deadlines were not checked; pass --current-version to check them.",
+                unchecked
+            )
+            .dimmed()
+        );
+    }
+
+    overdue.is_empty()
+}
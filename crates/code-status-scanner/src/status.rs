@@ -0,0 +1,48 @@
+//! Expansion of `#[status(untested, needs = "hashing")]` into the markers it lists.
+
+use proc_macro2::TokenStream;
+use syn::{parse::Parser, punctuated::Punctuated, Expr, Lit, Meta, Token};
+
+/// The markers listed in a `status` attribute, as `(name, argument)` pairs
+///
+/// Arguments are in the decoded `("...")` form the scan stores for an
+/// individual marker, or empty for bare ones, so each entry gets the same
+/// finding ID as its individual form. Names outside `macro_names` are skipped,
+/// and so is an argument list that doesn't parse.
+pub fn entries(tokens: TokenStream, macro_names: &[&str]) -> Vec<(String, String)> {
+    let Ok(metas) = Punctuated::<Meta, Token![,]>::parse_terminated.parse2(tokens) else {
+        return Vec::new();
+    };
+
+    metas
+        .iter()
+        .filter_map(|meta| {
+            let argument = match meta {
+                Meta::Path(_) => String::new(),
+                Meta::NameValue(name_value) => match &name_value.value {
+                    Expr::Lit(syn::ExprLit {
+                        lit: Lit::Str(lit), ..
+                    }) => format!("(\"{}\")", lit.value()),
+                    _ => return None,
+                },
+                Meta::List(_) => return None,
+            };
+            let name = meta.path().get_ident()?.to_string();
+            macro_names
+                .contains(&name.as_str())
+                .then_some((name, argument))
+        })
+        .collect()
+}
+
+/// The markers listed in a `status` argument captured by the regex engine, such as `(untested)`
+pub fn entries_from_text(argument: &str, macro_names: &[&str]) -> Vec<(String, String)> {
+    let inner = argument
+        .trim()
+        .strip_prefix('(')
+        .and_then(|rest| rest.strip_suffix(')'))
+        .unwrap_or_default();
+    inner
+        .parse()
+        .map_or_else(|_| Vec::new(), |tokens| entries(tokens, macro_names))
+}
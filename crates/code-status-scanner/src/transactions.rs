@@ -0,0 +1,215 @@
+//! Best-effort check that `requires_transaction` functions run inside a transaction.
+//!
+//! This is a name-based heuristic over the parsed source: a function "opens a
+//! transaction" when its body mentions an identifier such as `transaction`,
+//! `tx`, `txn` or `begin*`, and it is "in a transactional scope" when every
+//! caller in the same file opens a transaction or is itself annotated.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::Path,
+};
+
+use syn::{visit::Visit, ExprCall, ExprMethodCall, Ident, ImplItemFn, ItemFn};
+
+/// What a function body does, as far as the heuristic is concerned
+#[derive(Default)]
+struct FnFacts {
+    opens_transaction: bool,
+    callees: HashSet<String>,
+}
+
+/// Collects [`FnFacts`] for every named function in a file
+#[derive(Default)]
+struct FnCollector {
+    functions: HashMap<String, FnFacts>,
+}
+
+/// Scans a single function body
+#[derive(Default)]
+struct BodyScanner {
+    facts: FnFacts,
+}
+
+impl<'ast> Visit<'ast> for FnCollector {
+    fn visit_item_fn(&mut self, node: &'ast ItemFn) {
+        let mut scanner = BodyScanner::default();
+        scanner.visit_block(&node.block);
+        self.functions
+            .insert(node.sig.ident.to_string(), scanner.facts);
+        syn::visit::visit_item_fn(self, node);
+    }
+
+    fn visit_impl_item_fn(&mut self, node: &'ast ImplItemFn) {
+        let mut scanner = BodyScanner::default();
+        scanner.visit_block(&node.block);
+        self.functions
+            .insert(node.sig.ident.to_string(), scanner.facts);
+        syn::visit::visit_impl_item_fn(self, node);
+    }
+}
+
+impl<'ast> Visit<'ast> for BodyScanner {
+    fn visit_ident(&mut self, ident: &'ast Ident) {
+        if is_transaction_ident(&ident.to_string()) {
+            self.facts.opens_transaction = true;
+        }
+    }
+
+    fn visit_expr_call(&mut self, node: &'ast ExprCall) {
+        if let syn::Expr::Path(path) = node.func.as_ref() {
+            if let Some(segment) = path.path.segments.last() {
+                self.facts.callees.insert(segment.ident.to_string());
+            }
+        }
+        syn::visit::visit_expr_call(self, node);
+    }
+
+    fn visit_expr_method_call(&mut self, node: &'ast ExprMethodCall) {
+        self.facts.callees.insert(node.method.to_string());
+        syn::visit::visit_expr_method_call(self, node);
+    }
+}
+
+/// Whether an identifier looks like it opens or refers to a transaction
+fn is_transaction_ident(ident: &str) -> bool {
+    let ident = ident.to_lowercase();
+    ident.contains("transaction")
+        || ident.starts_with("begin")
+        || ident.split('_').any(|part| part == "tx" || part == "txn")
+}
+
+/// Outcome of checking one annotated function
+#[derive(Debug, PartialEq, Eq)]
+pub enum Finding {
+    /// The function body opens a transaction itself
+    OpensTransaction,
+    /// Every caller in the file opens a transaction or is annotated
+    CalledFromTransactions,
+    /// Neither the body nor its callers show evidence of a transaction
+    NoTransaction { callers: usize },
+    /// The function could not be found in the parsed file
+    Unresolved,
+}
+
+/// Check the named functions in `file`, each of which carries `requires_transaction`
+///
+/// Returns `None` when the file cannot be read or parsed.
+pub fn check_file(file: &Path, annotated: &[&str]) -> Option<HashMap<String, Finding>> {
+    let content = fs::read_to_string(file).ok()?;
+    check_source(&content, annotated)
+}
+
+/// Check the named functions in a file's contents
+///
+/// Returns `None` when the contents cannot be parsed.
+fn check_source(content: &str, annotated: &[&str]) -> Option<HashMap<String, Finding>> {
+    let ast = syn::parse_file(content).ok()?;
+
+    let mut collector = FnCollector::default();
+    collector.visit_file(&ast);
+    let functions = collector.functions;
+
+    let findings = annotated
+        .iter()
+        .map(|&name| {
+            let finding = match functions.get(name) {
+                None => Finding::Unresolved,
+                Some(facts) if facts.opens_transaction => Finding::OpensTransaction,
+                Some(_) => {
+                    let callers: Vec<_> = functions
+                        .iter()
+                        .filter(|(caller, facts)| {
+                            caller.as_str() != name && facts.callees.contains(name)
+                        })
+                        .collect();
+                    let all_transactional = callers.iter().all(|(caller, facts)| {
+                        facts.opens_transaction || annotated.contains(&caller.as_str())
+                    });
+
+                    if !callers.is_empty() && all_transactional {
+                        Finding::CalledFromTransactions
+                    } else {
+                        Finding::NoTransaction {
+                            callers: callers.len(),
+                        }
+                    }
+                },
+            };
+            (name.to_string(), finding)
+        })
+        .collect();
+
+    Some(findings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = r#"
+#[requires_transaction("debits and credits together")]
+fn transfer(db: &Db) {
+    let tx = db.begin();
+    debit(&tx);
+    tx.commit();
+}
+
+#[requires_transaction("part of a transfer")]
+fn debit(tx: &Tx) {
+    apply(tx);
+}
+
+#[requires_transaction("must be atomic")]
+fn apply(conn: &Conn) {
+    conn.execute("UPDATE");
+}
+
+#[requires_transaction("audit entry")]
+fn audit(conn: &Conn) {
+    conn.execute("INSERT");
+}
+
+#[requires_transaction("rebuild index")]
+fn reindex(conn: &Conn) {
+    conn.execute("REINDEX");
+}
+
+fn nightly(conn: &Conn) {
+    audit(conn);
+}
+"#;
+
+    fn check(name: &str) -> Finding {
+        let annotated = ["transfer", "debit", "apply", "audit", "reindex", "missing"];
+        check_source(SOURCE, &annotated)
+            .unwrap()
+            .remove(name)
+            .unwrap()
+    }
+
+    #[test]
+    fn function_opening_a_transaction_is_transactional() {
+        assert_eq!(check("transfer"), Finding::OpensTransaction);
+        // A transaction handle passed in counts as running inside one
+        assert_eq!(check("debit"), Finding::OpensTransaction);
+    }
+
+    #[test]
+    fn function_called_only_from_transactional_code_is_transactional() {
+        assert_eq!(check("apply"), Finding::CalledFromTransactions);
+    }
+
+    #[test]
+    fn function_without_transactional_callers_is_flagged() {
+        assert_eq!(check("audit"), Finding::NoTransaction { callers: 1 });
+        assert_eq!(check("reindex"), Finding::NoTransaction { callers: 0 });
+        assert_eq!(check("missing"), Finding::Unresolved);
+    }
+
+    #[test]
+    fn unparsable_source_is_not_checked() {
+        assert!(check_source("fn broken(", &["broken"]).is_none());
+    }
+}
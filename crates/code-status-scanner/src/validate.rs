@@ -0,0 +1,234 @@
+//! Consistency rules that relate markers to one another.
+
+use std::{collections::BTreeMap, path::Path};
+
+use colored::Colorize;
+
+use crate::{
+    transactions::{self, Finding},
+    MacroInstance,
+};
+
+/// A rule violation tied to the marker that triggered it
+struct Violation<'a> {
+    instance: &'a MacroInstance,
+    message: String,
+}
+
+/// Run every validation rule and print the violations
+///
+/// Finding paths are relative to `root`. Returns `true` when no rule was violated.
+pub fn report(instances: &[MacroInstance], root: &Path) -> bool {
+    let mut violations = unmatched_migrations(instances);
+    violations.extend(transactions_without_context(instances, root));
+
+    if violations.is_empty() {
+        println!("{}", "All validation rules passed.".green());
+        return true;
+    }
+
+    println!(
+        "{}",
+        format!("Found {} validation issues:", violations.len()).red()
+    );
+    println!();
+
+    for violation in &violations {
+        println!(
+            "{} {}",
+            format!(
+                "{}:{}",
+                violation.instance.path.display(),
+                violation.instance.line
+            )
+            .blue(),
+            violation.message.yellow()
+        );
+        println!("    {}", violation.instance.context_block("    "));
+        println!();
+    }
+
+    false
+}
+
+/// Normalize a free-form description for matching: case- and whitespace-insensitive
+fn normalize(description: &str) -> String {
+    description
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// `requires_migration` markers with no `migration_complete` sharing their description
+pub fn pending_migrations(instances: &[MacroInstance]) -> Vec<&MacroInstance> {
+    let completed: Vec<String> = instances
+        .iter()
+        .filter(|i| i.macro_name == "migration_complete")
+        .filter_map(|i| i.argument_value().map(normalize))
+        .collect();
+
+    instances
+        .iter()
+        .filter(|i| i.macro_name == "requires_migration")
+        .filter(|i| {
+            let description = normalize(i.argument_value().unwrap_or_default());
+            !completed.contains(&description)
+        })
+        .collect()
+}
+
+/// Flag `requires_migration` markers with no `migration_complete` sharing their description
+fn unmatched_migrations(instances: &[MacroInstance]) -> Vec<Violation<'_>> {
+    pending_migrations(instances)
+        .into_iter()
+        .map(|instance| Violation {
+            instance,
+            message: format!(
+                "requires_migration(\"{}\") has no matching migration_complete",
+                instance.argument_value().unwrap_or_default()
+            ),
+        })
+        .collect()
+}
+
+/// Flag `requires_transaction` functions that neither open a transaction nor
+/// are only called from transactional code (best-effort, per file)
+fn transactions_without_context<'a>(
+    instances: &'a [MacroInstance],
+    root: &Path,
+) -> Vec<Violation<'a>> {
+    let mut by_file: BTreeMap<_, Vec<&MacroInstance>> = BTreeMap::new();
+    for instance in instances
+        .iter()
+        .filter(|i| i.macro_name == "requires_transaction")
+    {
+        by_file.entry(&instance.path).or_default().push(instance);
+    }
+
+    let mut violations = Vec::new();
+    for (file, annotated) in by_file {
+        let names: Vec<&str> = annotated
+            .iter()
+            .filter_map(|i| i.item_name.as_deref())
+            .collect();
+        let findings = match transactions::check_file(&root.join(file), &names) {
+            Some(findings) => findings,
+            None => continue,
+        };
+
+        for instance in annotated {
+            let name = match instance.item_name.as_deref() {
+                Some(name) => name,
+                None => continue,
+            };
+            if let Some(Finding::NoTransaction { callers }) = findings.get(name) {
+                let detail = if *callers == 0 {
+                    "and has no callers in this file".to_string()
+                } else {
+                    format!("and {} caller(s) run outside a transaction", callers)
+                };
+                violations.push(Violation {
+                    instance,
+                    message: format!(
+                        "requires_transaction function `{}` opens no transaction {}",
+                        name, detail
+                    ),
+                });
+            }
+        }
+    }
+
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use code_status_scanner::Scanner;
+
+    use super::*;
+
+    fn scan(source: &str) -> Vec<MacroInstance> {
+        let file = Path::new("src/lib.rs");
+        Scanner::new().scan_source(file, file, source)
+    }
+
+    #[test]
+    fn completed_migration_matches_ignoring_case_and_spacing() {
+        let instances = scan(
+            r#"
+#[requires_migration("Split  users table")]
+fn migrate_users() {}
+
+#[migration_complete("split users   TABLE")]
+fn users_split() {}
+"#,
+        );
+
+        assert!(pending_migrations(&instances).is_empty());
+        assert!(unmatched_migrations(&instances).is_empty());
+    }
+
+    #[test]
+    fn orphaned_migration_is_reported() {
+        let instances = scan(
+            r#"
+#[requires_migration("split users table")]
+fn migrate_users() {}
+
+#[requires_migration("drop legacy ids")]
+fn drop_ids() {}
+
+#[migration_complete("split users table")]
+fn users_split() {}
+"#,
+        );
+
+        let pending: Vec<_> = pending_migrations(&instances)
+            .iter()
+            .map(|i| i.item_name.as_deref().unwrap())
+            .collect();
+        assert_eq!(pending, ["drop_ids"]);
+
+        let violations = unmatched_migrations(&instances);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(
+            violations[0].message,
+            "requires_migration(\"drop legacy ids\") has no matching migration_complete"
+        );
+    }
+
+    #[test]
+    fn transaction_rule_reports_functions_without_a_transaction() {
+        let root = tempfile::tempdir().unwrap();
+        let source = r#"
+#[requires_transaction("debits and credits together")]
+fn transfer(db: &Db) {
+    let txn = db.begin();
+    apply(&txn);
+}
+
+#[requires_transaction("must be atomic")]
+fn apply(conn: &Conn) {
+    conn.execute("UPDATE");
+}
+
+#[requires_transaction("rebuild index")]
+fn reindex(conn: &Conn) {
+    conn.execute("REINDEX");
+}
+"#;
+        std::fs::create_dir(root.path().join("src")).unwrap();
+        std::fs::write(root.path().join("src/lib.rs"), source).unwrap();
+
+        let instances = scan(source);
+        let messages: Vec<_> = transactions_without_context(&instances, root.path())
+            .into_iter()
+            .map(|v| v.message)
+            .collect();
+        assert_eq!(
+            messages,
+            ["requires_transaction function `reindex` opens no transaction and has no callers in this file"]
+        );
+    }
+}
@@ -0,0 +1,76 @@
+//! Re-running a report whenever a scanned file changes, for the `watch` subcommand.
+
+use std::{
+    path::Path,
+    sync::mpsc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use colored::Colorize;
+use notify::{EventKind, RecursiveMode, Watcher};
+
+use crate::date::Date;
+
+/// How long the tree has to stay quiet before a burst of changes triggers a rescan
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Run `report` now and again after every burst of changes under `path`
+///
+/// Only changes to files `is_scanned` accepts trigger a run. The screen is
+/// cleared before each run, which is headed by the time it started. Returns
+/// when the watcher fails; otherwise runs until the process is interrupted.
+pub fn run(
+    path: &Path,
+    is_scanned: impl Fn(&Path) -> bool,
+    mut report: impl FnMut(),
+) -> Result<(), String> {
+    let (sender, events) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(sender).map_err(|err| err.to_string())?;
+    watcher
+        .watch(path, RecursiveMode::Recursive)
+        .map_err(|err| format!("{}: {}", path.display(), err))?;
+
+    loop {
+        print!("\x1b[2J\x1b[H");
+        println!(
+            "{}",
+            format!(
+                "[{}] Watching {} for changes (Ctrl-C to stop)",
+                timestamp(),
+                path.display()
+            )
+            .dimmed()
+        );
+        println!();
+        report();
+
+        // Wait for a relevant change, then for the burst it starts to settle
+        loop {
+            let event = events
+                .recv()
+                .map_err(|err| err.to_string())?
+                .map_err(|err| err.to_string())?;
+            if !matches!(event.kind, EventKind::Access(_))
+                && event.paths.iter().any(|changed| is_scanned(changed))
+            {
+                break;
+            }
+        }
+        while events.recv_timeout(DEBOUNCE).is_ok() {}
+    }
+}
+
+/// The current UTC date and time, like `2024-05-01 14:03:27 UTC`
+fn timestamp() -> String {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    let time_of_day = secs % 86_400;
+    format!(
+        "{} {:02}:{:02}:{:02} UTC",
+        Date::from_unix_seconds(secs as i64),
+        time_of_day / 3600,
+        time_of_day / 60 % 60,
+        time_of_day % 60
+    )
+}
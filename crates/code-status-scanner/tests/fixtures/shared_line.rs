@@ -0,0 +1,7 @@
+//! Several markers sharing one line.
+
+#[untested] #[needs("error handling")]
+fn parse() {}
+
+#[perf_critical("hot loop")] #[untested]
+fn render() {}
@@ -0,0 +1,10 @@
+//! Markers whose arguments need Rust string literal decoding.
+
+#[assumptions(r#"input has "quoted" keys like [a] and [b]"#)]
+fn parse_keys() {}
+
+#[needs("it\"s tricky")]
+fn escape() {}
+
+#[needs(r##"see "#[needs]" in https://example.com/t/1?q=[x]"##)]
+fn link() {}
@@ -0,0 +1,81 @@
+//! Scans of the files under `tests/fixtures`.
+
+use std::path::{Path, PathBuf};
+
+use code_status_scanner::{Engine, MacroInstance, Scanner};
+
+type Finding = (usize, String, Option<String>, Option<String>);
+
+fn fixture(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(name)
+}
+
+/// `(line, macro, argument, item)` of each finding in a fixture, with either engine
+///
+/// The regex engine records a bare attribute's argument as empty and the AST
+/// engine as missing, so both read as `None` here.
+fn scan(name: &str, engine: Engine) -> Vec<Finding> {
+    Scanner::new()
+        .engine(engine)
+        .scan(&fixture(name))
+        .into_iter()
+        .map(|instance: MacroInstance| {
+            (
+                instance.line,
+                instance.macro_name,
+                instance.argument.filter(|argument| !argument.is_empty()),
+                instance.item_name,
+            )
+        })
+        .collect()
+}
+
+fn finding(line: usize, macro_name: &str, argument: Option<&str>, item: &str) -> Finding {
+    (
+        line,
+        macro_name.to_string(),
+        argument.map(str::to_string),
+        Some(item.to_string()),
+    )
+}
+
+#[test]
+fn markers_sharing_a_line_are_each_reported_once() {
+    for engine in [Engine::Regex, Engine::Ast] {
+        assert_eq!(
+            scan("shared_line.rs", engine),
+            [
+                finding(3, "untested", None, "parse"),
+                finding(3, "needs", Some("(\"error handling\")"), "parse"),
+                finding(6, "perf_critical", Some("(\"hot loop\")"), "render"),
+                finding(6, "untested", None, "render"),
+            ],
+            "{:?}",
+            engine
+        );
+    }
+}
+
+#[test]
+fn string_arguments_are_decoded() {
+    for engine in [Engine::Regex, Engine::Ast] {
+        let values: Vec<_> = Scanner::new()
+            .engine(engine)
+            .scan(&fixture("string_arguments.rs"))
+            .iter()
+            .map(|instance| instance.argument_value().map(str::to_string))
+            .collect();
+        assert_eq!(
+            values,
+            [
+                Some(r#"input has "quoted" keys like [a] and [b]"#.to_string()),
+                Some(r#"it"s tricky"#.to_string()),
+                Some(r##"see "#[needs]" in https://example.com/t/1?q=[x]"##.to_string()),
+            ],
+            "{:?}",
+            engine
+        );
+    }
+}
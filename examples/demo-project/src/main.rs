@@ -36,14 +36,13 @@ fn parse_config(config_str: &str) -> Vec<String> {
     // This function unwraps results without proper error handling
     config_str
         .lines()
-        .map(|line| line.split('=').collect::<Vec<&str>>())
-        .filter(|parts| parts.len() == 2)
-        .map(|parts| parts[1].trim().to_string())
+        .filter(|line| line.contains('='))
+        .map(|line| line.split_once('=').unwrap().1.trim().to_string())
         .collect()
 }
 
 #[needs("proper error handling")]
-#[needs("input validation")]
+#[needs(reason = "input validation", issue = "DEMO-42", priority = "high")]
 fn process_user_input(input: &str) -> Result<(), String> {
     // This function needs multiple improvements
     if input.is_empty() {
@@ -147,6 +146,31 @@ fn calculate_average(sum: f64, count: usize) -> f64 {
     sum / count as f64
 }
 
+#[requires_cleanup_on_error("remove the partially written file")]
+#[panic_path("panics if the chunk list is empty")]
+fn write_chunks(chunks: &[Vec<u8>]) -> Vec<u8> {
+    // A panic midway would leave a truncated output behind
+    let mut out = Vec::with_capacity(chunks[0].len() * chunks.len());
+    for chunk in chunks {
+        out.extend_from_slice(chunk);
+    }
+    out
+}
+
+// Markers sharing a line are reported separately, each with its own argument
+#[rustfmt::skip]
+#[untested] #[needs("reject values over 100")]
+fn clamp_percent(value: u8) -> u8 {
+    value.min(100)
+}
+
+// Raw strings and escapes are reported as the string they stand for
+#[assumptions(r#"keys arrive quoted, like "[tenant]""#)]
+#[needs("accept \"unquoted\" keys from v1 clients")]
+fn parse_key(raw: &str) -> &str {
+    raw.trim_matches('"')
+}
+
 // =====================================
 // Review & Future Work Markers Examples
 // =====================================
@@ -192,6 +216,12 @@ fn binary_search(arr: &[i32], target: i32) -> Option<usize> {
     None
 }
 
+#[assumptions("!samples.is_empty()")]
+fn first_sample(samples: &[f64]) -> f64 {
+    // With the check-assumptions feature this assumption becomes a debug_assert!
+    samples[0]
+}
+
 #[revisit_in("v2.0")]
 fn current_implementation() -> String {
     // Current implementation that should be revisited in version 2.0
@@ -206,30 +236,26 @@ fn uses_external_library() -> String {
 
 #[platform_specific("unix")]
 fn unix_only_function() -> &'static str {
-    // This function is meant for Unix-like systems only
-    #[cfg(unix)]
-    {
-        return "Running on Unix";
-    }
+    // Only compiled on Unix-like systems
+    "Running on Unix"
+}
 
-    #[cfg(not(unix))]
-    {
-        panic!("This function is Unix-only");
-    }
+#[platform_specific("linux, macos", soft)]
+fn default_config_dir() -> &'static str {
+    // Compiles everywhere; the soft form only documents the platform dependency
+    "~/.config"
 }
 
 #[feature_gated("advanced-features")]
 fn advanced_feature() -> &'static str {
-    // This function should only be available when the "advanced-features" feature is enabled
-    #[cfg(feature = "advanced-features")]
-    {
-        return "Advanced feature enabled";
-    }
+    // Only compiled when the "advanced-features" feature is enabled
+    "Advanced feature enabled"
+}
 
-    #[cfg(not(feature = "advanced-features"))]
-    {
-        return "Advanced feature not enabled";
-    }
+#[cfg(not(feature = "advanced-features"))]
+fn advanced_feature() -> &'static str {
+    // Fallback compiled in place of the gated version
+    "Advanced feature not enabled"
 }
 
 #[api_stability("experimental")]
@@ -263,6 +289,303 @@ fn render_frame(scene: &[u8]) -> Vec<u8> {
     frame
 }
 
+#[requires_locale("date order differs between locales")]
+fn format_short_date(day: u32, month: u32) -> String {
+    // Hard-codes day/month ordering
+    format!("{:02}/{:02}", day, month)
+}
+
+#[requires_transaction("balance and ledger must change together")]
+fn apply_transfer(
+    balances: &mut [i64],
+    ledger: &mut Vec<String>,
+    from: usize,
+    to: usize,
+    amount: i64,
+) {
+    // Callers are expected to wrap this in a transaction
+    balances[from] -= amount;
+    balances[to] += amount;
+    ledger.push(format!("{} -> {}: {}", from, to, amount));
+}
+
+fn transfer(balances: &mut [i64], ledger: &mut Vec<String>, from: usize, to: usize, amount: i64) {
+    // Opens the transaction that apply_transfer requires
+    let transaction_log_start = ledger.len();
+    apply_transfer(balances, ledger, from, to, amount);
+    ledger.truncate(transaction_log_start + 1);
+}
+
+#[requires_review_before("2030-06-30")]
+fn rotate_signing_keys(keys: &mut Vec<u64>) {
+    // Key rotation must be reviewed before the next audit
+    keys.rotate_left(1);
+}
+
+#[requires_accessibility_review("status conveyed by color alone")]
+fn render_status_badge(ok: bool) -> String {
+    // Red/green only; needs a text or symbol alternative
+    if ok {
+        "\x1b[32m●\x1b[0m".into()
+    } else {
+        "\x1b[31m●\x1b[0m".into()
+    }
+}
+
+#[unsafe_usage("transmutes a byte array into a packed header")]
+#[reviewed("2024-03-01 by alice")]
+fn header_from_bytes(bytes: [u8; 4]) -> u32 {
+    // Reviewed: every bit pattern is a valid u32
+    unsafe { std::mem::transmute::<[u8; 4], u32>(bytes) }
+}
+
+/// Returns the greeting shown on the landing page, e.g. `greeting("Ada")`.
+#[requires_documentation_review("doc example is not a doctest")]
+fn greeting(name: &str) -> String {
+    // The doc comment example above is never compiled or checked
+    format!("Hello, {}!", name)
+}
+
+#[requires_feature_documentation("DEMO-7 bulk export")]
+fn export_all(records: &[String]) -> String {
+    // New user-facing export; needs a changelog entry before release
+    records.join("\n")
+}
+
+// =====================================
+// Testing Markers Examples
+// =====================================
+
+#[requires_fuzzing("untrusted header bytes")]
+fn parse_header(bytes: &[u8]) -> Option<(u8, u16)> {
+    // Parses a three byte header from untrusted input
+    match bytes {
+        [version, hi, lo, ..] => Some((*version, u16::from_be_bytes([*hi, *lo]))),
+        _ => None,
+    }
+}
+
+#[requires_backward_compat_test("v1 clients send comma-separated tags")]
+fn parse_tags(raw: &str) -> Vec<String> {
+    // Accepts both the v1 comma format and the v2 semicolon format
+    raw.split([',', ';'])
+        .map(|tag| tag.trim().to_string())
+        .collect()
+}
+
+#[requires_schema_migration_test("orders.total moves from f64 to integer cents")]
+struct OrderRow {
+    id: u64,
+    total_cents: i64,
+}
+
+#[requires_property_test("roundtrips through parse_tags")]
+fn format_tags(tags: &[String]) -> String {
+    // The inverse of parse_tags for the v1 comma format
+    tags.join(",")
+}
+
+#[needs_tests(have = 2, want = 5)]
+fn merge_tags(left: &[String], right: &[String]) -> Vec<String> {
+    let mut merged: Vec<String> = left.iter().chain(right).cloned().collect();
+    merged.sort();
+    merged.dedup();
+    merged
+}
+
+#[needs_tests(3)]
+fn normalize_tag(tag: &str) -> String {
+    tag.trim().to_lowercase()
+}
+
+/// Retries are only hit against a flaky upstream, which the test suite stubs out
+#[coverage_gap]
+fn retry_with_backoff(attempts: u32) -> u32 {
+    (0..attempts).map(|attempt| 1 << attempt).sum()
+}
+
+// =====================================
+// Performance Markers Examples
+// =====================================
+
+#[requires_capacity_planning("memory scales with tenants")]
+fn build_tenant_cache(tenant_ids: &[u64]) -> Vec<(u64, Vec<u8>)> {
+    // Allocates a cache entry per tenant
+    tenant_ids.iter().map(|&id| (id, vec![0; 1024])).collect()
+}
+
+#[approximation("first-order Taylor expansion, accurate near zero")]
+fn approx_sin(x: f64) -> f64 {
+    // Only accurate for small angles
+    x
+}
+
+#[numeric_precision("f32 sufficient: positions are rounded to whole pixels")]
+fn scale_position(position: f32, zoom: f32) -> f32 {
+    // Single precision is plenty for screen coordinates
+    position * zoom
+}
+
+#[requires_load_test("before GA")]
+fn checkout(cart: &[(u64, u32)]) -> u64 {
+    // Every purchase goes through here at peak traffic
+    cart.iter()
+        .map(|&(price, quantity)| price * u64::from(quantity))
+        .sum()
+}
+
+#[requires_metrics("p99 latency histogram per endpoint")]
+#[observed("requests_total counter")]
+#[perf_critical]
+fn route_request(path: &str) -> u16 {
+    // Hot path for every incoming request
+    if path.starts_with("/api/") {
+        200
+    } else {
+        404
+    }
+}
+
+// =====================================
+// Lifecycle Markers Examples
+// =====================================
+
+#[legacy_compat("pre-2.0 export clients")]
+#[deprecated_since("2.0.0")]
+#[requires_feature_sunset("2030-01-01")]
+fn legacy_export_format(records: &[String]) -> String {
+    // The legacy export format is scheduled for removal
+    records.join(";")
+}
+
+#[requires_migration("move sessions to the new store")]
+fn load_session_legacy(id: u64) -> Option<String> {
+    // Reads from the old session table until the migration lands
+    Some(format!("legacy-session-{}", id))
+}
+
+#[migration_complete("move sessions to the new store")]
+fn load_session(id: u64) -> Option<String> {
+    // Reads from the new session store
+    Some(format!("session-{}", id))
+}
+
+#[replaces("legacy_data_conversion")]
+fn convert_data(data: &str) -> String {
+    // Replacement for the temporary conversion helper
+    data.split([',', ';']).collect::<Vec<_>>().join("|")
+}
+
+#[requires_feature_parity("matches the python exporter's quoting rules")]
+fn export_csv_row(fields: &[&str]) -> String {
+    // Ported from the python implementation; quoting is not yet identical
+    fields.join(",")
+}
+
+/// Returns the records instead of writing them to a caller-supplied buffer
+#[breaking_change("export_records no longer takes an output buffer")]
+pub fn export_records(records: &[String]) -> Vec<String> {
+    records.to_vec()
+}
+
+#[requires_rollback_plan("keep the v1 index until the v2 rebuild is verified")]
+#[needs_benchmark_before_merge("index rebuild throughput")]
+fn rebuild_index(records: &[String]) -> Vec<String> {
+    let mut index = records.to_vec();
+    index.sort();
+    index
+}
+
+// =====================================
+// Concurrency Markers Examples
+// =====================================
+
+#[data_race_risk("counter is bumped from worker threads without a lock")]
+struct HitCounter {
+    hits: std::cell::Cell<u64>,
+}
+
+#[requires_ordering("publish the payload before setting the ready flag")]
+fn publish(payload: &std::sync::atomic::AtomicU64, ready: &std::sync::atomic::AtomicBool) {
+    payload.store(42, std::sync::atomic::Ordering::Relaxed);
+    ready.store(true, std::sync::atomic::Ordering::Release);
+}
+
+#[blocking_io("reads the config file synchronously on the executor")]
+async fn load_settings(path: &str) -> std::io::Result<String> {
+    std::fs::read_to_string(path)
+}
+
+#[not_thread_safe("caches parsed templates in an Rc")]
+struct TemplateCache {
+    templates: std::rc::Rc<std::collections::HashMap<String, String>>,
+}
+
+// =====================================
+// Security Markers Examples
+// =====================================
+
+#[requires_permission_check("admin only")]
+#[security_sensitive]
+fn delete_account(accounts: &mut Vec<u64>, id: u64) {
+    // Only administrators may delete accounts
+    accounts.retain(|&account| account != id);
+}
+
+#[requires_secret("SMTP_PASSWORD")]
+#[security_sensitive]
+fn send_password_reset(email: &str) -> String {
+    // Authenticates against the mail relay with a stored password
+    format!("reset link sent to {}", email)
+}
+
+#[requires_input_sanitization("SQL")]
+fn find_user_query(username: &str) -> String {
+    // Interpolates user input straight into the query text
+    format!("SELECT * FROM users WHERE name = '{}'", username)
+}
+
+// =====================================
+// Resilience Markers Examples
+// =====================================
+
+#[requires_graceful_shutdown("flush queued jobs before exiting")]
+struct JobQueue {
+    pending: Vec<String>,
+}
+
+#[requires_circuit_breaker("geocoding-service")]
+#[dependency_sensitive]
+fn lookup_coordinates(address: &str) -> Option<(f64, f64)> {
+    // Calls an external geocoder that can hang under load
+    if address.is_empty() {
+        None
+    } else {
+        Some((0.0, 0.0))
+    }
+}
+
+#[requires_chaos_test("network partition")]
+fn elect_leader(votes: &[(u64, u64)]) -> Option<u64> {
+    // Split votes during a partition could elect two leaders
+    votes
+        .iter()
+        .max_by_key(|&&(_, count)| count)
+        .map(|&(node, _)| node)
+}
+
+// =====================================
+// Ownership Markers Examples
+// =====================================
+
+#[owner("payments-team")]
+#[owner("alice")]
+#[needs_review("refund rounding")]
+#[untested]
+fn refund(amount_cents: u64) -> u64 {
+    amount_cents
+}
+
 // =====================================
 // Example Struct with Multiple Markers
 // =====================================
@@ -307,6 +630,32 @@ trait DataProcessor {
     fn validate(&self, data: &[u8]) -> bool;
 }
 
+// =====================================
+// Several Markers in One Attribute
+// =====================================
+
+#[status(untested, needs = "streaming input", complexity = "O(n log n)")]
+fn sort_records(records: &mut [u64]) {
+    records.sort_unstable();
+}
+
+// =====================================
+// Text the Scanner Ignores
+// =====================================
+
+// A commented-out marker is not a finding:
+// #[panic_path("when the cache is empty")]
+
+/// Neither is a marker in a doc example:
+///
+/// ```ignore
+/// #[untested]
+/// fn example() {}
+/// ```
+fn marker_lookalike() -> &'static str {
+    "#[needs(\"text in a string literal\")]"
+}
+
 // =====================================
 // Main Function
 // =====================================